@@ -5,16 +5,19 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
+use crate::{EntryKind, RouteInfo, RouteKind};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default = "default_project_path")]
     pub project: String,
 
-    #[serde(default = "default_xml_output")]
-    pub xml_output: String,
+    // Unset outputs go to the detected framework's default locations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub xml_output: Option<String>,
 
-    #[serde(default = "default_json_output")]
-    pub json_output: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_output: Option<String>,
 
     #[serde(default = "default_base_url")]
     pub base_url: String,
@@ -24,6 +27,139 @@ pub struct Config {
 
     #[serde(default)]
     pub custom_sitemaps: HashMap<String, CustomSitemap>,
+
+    // Which route source scans the project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framework: Option<Framework>,
+
+    #[serde(default)]
+    pub scanner: ScannerConfig,
+
+    #[serde(default)]
+    pub urls: UrlOverrides,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub i18n: Option<I18nConfig>,
+
+    #[serde(default)]
+    pub dynamic_params: HashMap<String, DynamicParamSource>,
+
+    #[serde(default)]
+    pub unresolved_routes: UnresolvedRoutes,
+
+    #[serde(default)]
+    pub included_routes: IncludedRoutes,
+
+    // Leave routes matched by the middleware matcher out of sitemap.xml
+    #[serde(default)]
+    pub exclude_guarded_routes: bool,
+}
+
+// Route handlers and metadata files are only written to sitemaps when they match these rules
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct IncludedRoutes {
+    #[serde(default)]
+    pub exact: Vec<String>,
+
+    #[serde(default)]
+    pub children: Vec<String>,
+
+    #[serde(default)]
+    pub patterns: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<RouteKind>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entries: Vec<EntryKind>,
+}
+
+// What happens to dynamic routes whose params were never expanded, per output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnresolvedRoutes {
+    #[serde(default = "default_unresolved_xml")]
+    pub xml: UnresolvedPolicy,
+
+    #[serde(default = "default_unresolved_json")]
+    pub json: UnresolvedPolicy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnresolvedPolicy {
+    Omit,
+    Keep,
+    Fail,
+}
+
+fn default_unresolved_xml() -> UnresolvedPolicy {
+    UnresolvedPolicy::Omit
+}
+
+fn default_unresolved_json() -> UnresolvedPolicy {
+    UnresolvedPolicy::Keep
+}
+
+impl Default for UnresolvedRoutes {
+    fn default() -> Self {
+        Self {
+            xml: default_unresolved_xml(),
+            json: default_unresolved_json(),
+        }
+    }
+}
+
+impl UnresolvedPolicy {
+    pub fn apply(self, routes: Vec<RouteInfo>, output: &str) -> Result<Vec<RouteInfo>> {
+        match self {
+            UnresolvedPolicy::Keep => Ok(routes),
+            UnresolvedPolicy::Omit => Ok(routes
+                .into_iter()
+                .filter(|route| route.params.is_empty())
+                .collect()),
+            UnresolvedPolicy::Fail => {
+                let unresolved: Vec<&str> = routes
+                    .iter()
+                    .filter(|route| !route.params.is_empty())
+                    .map(|route| route.route.as_str())
+                    .collect();
+                if !unresolved.is_empty() {
+                    anyhow::bail!(
+                        "{} has unresolved dynamic routes: {}",
+                        output,
+                        unresolved.join(", ")
+                    );
+                }
+                Ok(routes)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScannerConfig {
+    #[serde(default)]
+    pub include_intercepting_routes: bool,
+
+    #[serde(default = "default_true")]
+    pub respect_gitignore: bool,
+
+    #[serde(default)]
+    pub follow_symlinks: bool,
+}
+
+impl Default for ScannerConfig {
+    fn default() -> Self {
+        Self {
+            include_intercepting_routes: false,
+            respect_gitignore: true,
+            follow_symlinks: false,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -36,6 +172,9 @@ pub struct ExcludedRoutes {
 
     #[serde(default)]
     pub patterns: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<RouteKind>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,33 +201,128 @@ pub struct CustomRoutes {
 
     #[serde(default)]
     pub patterns: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<RouteKind>,
 }
 
 fn default_project_path() -> String {
     ".".to_string()
 }
 
-fn default_xml_output() -> String {
-    "sitemap.xml".to_string()
+fn default_base_url() -> String {
+    "https://example.com".to_string()
 }
 
-fn default_json_output() -> String {
-    "sitemap.json".to_string()
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Framework {
+    #[default]
+    Next,
+    SvelteKit,
+    Nuxt,
 }
 
-fn default_base_url() -> String {
-    "https://example.com".to_string()
+// Overrides for URL settings otherwise read from the framework's config
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct UrlOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_path: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trailing_slash: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub static_export: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct I18nConfig {
+    pub locales: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_locale: Option<String>,
+
+    #[serde(default = "default_locale_param")]
+    pub param: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix_default_locale: Option<bool>,
+}
+
+fn default_locale_param() -> String {
+    "locale".to_string()
+}
+
+// Where the values for a dynamic route's params come from, keyed by route (e.g. "/products/:id")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DynamicParamSource {
+    #[serde(flatten)]
+    pub provider: ParamProvider,
+
+    // Param name => field name, params without an entry use the field with the same name
+    #[serde(default)]
+    pub params: HashMap<String, String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lastmod: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ParamProvider {
+    Json {
+        file: String,
+
+        // JSON pointer to the array of records, the document root by default
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pointer: Option<String>,
+    },
+    Csv {
+        file: String,
+    },
+    Sqlite {
+        database: String,
+
+        // Each column of the result is a field, e.g. SELECT slug, updated_at FROM posts
+        query: String,
+    },
+    Command {
+        // Run through the shell when no args are given, e.g. "node scripts/list-slugs.mjs"
+        command: String,
+
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+
+        #[serde(default = "default_command_timeout")]
+        timeout_secs: u64,
+    },
+}
+
+fn default_command_timeout() -> u64 {
+    30
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             project: default_project_path(),
-            xml_output: default_xml_output(),
-            json_output: default_json_output(),
+            xml_output: None,
+            json_output: None,
             base_url: default_base_url(),
             excluded_routes: ExcludedRoutes::default(),
             custom_sitemaps: HashMap::new(),
+            framework: None,
+            scanner: ScannerConfig::default(),
+            urls: UrlOverrides::default(),
+            i18n: None,
+            dynamic_params: HashMap::new(),
+            unresolved_routes: UnresolvedRoutes::default(),
+            included_routes: IncludedRoutes::default(),
+            exclude_guarded_routes: false,
         }
     }
 }
@@ -132,36 +366,60 @@ impl Config {
     }
 
     // Check if a route should be excluded based on Category 1 rules
-    pub fn is_excluded(&self, route: &str) -> bool {
-        // Check exact matches
-        if self.excluded_routes.exact.contains(&route.to_string()) {
-            return true;
-        }
+    pub fn is_excluded(&self, route_info: &RouteInfo) -> bool {
+        let rules = &self.excluded_routes;
+        route_matches(
+            route_info,
+            &rules.exact,
+            &rules.children,
+            &rules.patterns,
+            &rules.kinds,
+        )
+    }
 
-        // Check children routes
-        for parent in &self.excluded_routes.children {
-            if route == parent || route.starts_with(&format!("{}/", parent)) {
-                return true;
-            }
+    // Pages are always candidates, other entries only when a rule includes them
+    pub fn is_included_entry(&self, route_info: &RouteInfo) -> bool {
+        if route_info.entry.is_page() {
+            return true;
         }
 
-        // Check regex patterns
-        for pattern in &self.excluded_routes.patterns {
-            if let Ok(regex) = Regex::new(pattern) {
-                if regex.is_match(route) {
-                    return true;
-                }
-            }
+        // Only handlers that answer GET or HEAD can be fetched, e.g. not a POST-only webhook
+        if route_info.entry == EntryKind::RouteHandler
+            && !route_info
+                .methods
+                .iter()
+                .any(|method| method == "GET" || method == "HEAD")
+        {
+            return false;
         }
 
-        false
+        let rules = &self.included_routes;
+        rules.entries.contains(&route_info.entry)
+            || route_matches(
+                route_info,
+                &rules.exact,
+                &rules.children,
+                &rules.patterns,
+                &rules.kinds,
+            )
     }
 
     // Check if a route belongs to a custom sitemap (Category 2)
-    pub fn get_matching_custom_sitemaps(&self, route: &str) -> Vec<String> {
+    pub fn get_matching_custom_sitemaps(&self, route_info: &RouteInfo) -> Vec<String> {
+        let route = route_info.route.as_str();
         let mut matches = Vec::new();
 
+        if !self.is_included_entry(route_info) {
+            return matches;
+        }
+
         for (key, custom) in &self.custom_sitemaps {
+            // Check route kinds
+            if custom.routes.kinds.contains(&route_info.kind()) {
+                matches.push(key.clone());
+                continue;
+            }
+
             // Check exact matches
             if custom.routes.exact.contains(&route.to_string()) {
                 matches.push(key.clone());
@@ -178,11 +436,11 @@ impl Config {
 
             // Check regex patterns
             for pattern in &custom.routes.patterns {
-                if let Ok(regex) = Regex::new(pattern) {
-                    if regex.is_match(route) {
-                        matches.push(key.clone());
-                        break;
-                    }
+                if let Ok(regex) = Regex::new(pattern)
+                    && regex.is_match(route)
+                {
+                    matches.push(key.clone());
+                    break;
                 }
             }
         }
@@ -191,9 +449,9 @@ impl Config {
     }
 
     // Check if a route should be included in the main JSON sitemap
-    pub fn include_in_main_json(&self, route: &str) -> bool {
+    pub fn include_in_main_json(&self, route: &RouteInfo) -> bool {
         // If it's in Category 1, exclude it
-        if self.is_excluded(route) {
+        if self.is_excluded(route) || !self.is_included_entry(route) {
             return false;
         }
 
@@ -202,10 +460,10 @@ impl Config {
         if !matching_sitemaps.is_empty() {
             // If any matching sitemap excludes from main JSON, exclude it
             for key in matching_sitemaps {
-                if let Some(custom) = self.custom_sitemaps.get(&key) {
-                    if !custom.include_in_main_json {
-                        return false;
-                    }
+                if let Some(custom) = self.custom_sitemaps.get(&key)
+                    && !custom.include_in_main_json
+                {
+                    return false;
                 }
             }
         }
@@ -214,9 +472,18 @@ impl Config {
     }
 
     // Check if a route should be included in the main XML sitemap
-    pub fn include_in_main_xml(&self, route: &str) -> bool {
+    pub fn include_in_main_xml(&self, route: &RouteInfo) -> bool {
         // If it's in Category 1, exclude it
-        if self.is_excluded(route) {
+        if self.is_excluded(route) || !self.is_included_entry(route) {
+            return false;
+        }
+
+        // Pages that declare noindex never belong in the XML sitemap
+        if route.noindex.is_some() {
+            return false;
+        }
+
+        if self.exclude_guarded_routes && route.guarded_by.is_some() {
             return false;
         }
 
@@ -225,10 +492,10 @@ impl Config {
         if !matching_sitemaps.is_empty() {
             // If any matching sitemap excludes from main XML, exclude it
             for key in matching_sitemaps {
-                if let Some(custom) = self.custom_sitemaps.get(&key) {
-                    if !custom.include_in_main_xml {
-                        return false;
-                    }
+                if let Some(custom) = self.custom_sitemaps.get(&key)
+                    && !custom.include_in_main_xml
+                {
+                    return false;
                 }
             }
         }
@@ -236,3 +503,37 @@ impl Config {
         true
     }
 }
+
+fn route_matches(
+    route_info: &RouteInfo,
+    exact: &[String],
+    children: &[String],
+    patterns: &[String],
+    kinds: &[RouteKind],
+) -> bool {
+    let route = route_info.route.as_str();
+
+    // Check route kinds
+    if kinds.contains(&route_info.kind()) {
+        return true;
+    }
+
+    // Check exact matches
+    if exact.iter().any(|exact| exact == route) {
+        return true;
+    }
+
+    // Check children routes
+    for parent in children {
+        if route == parent || route.starts_with(&format!("{}/", parent)) {
+            return true;
+        }
+    }
+
+    // Check regex patterns
+    patterns.iter().any(|pattern| {
+        Regex::new(pattern)
+            .map(|regex| regex.is_match(route))
+            .unwrap_or(false)
+    })
+}
@@ -0,0 +1,168 @@
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use crate::config::Framework;
+
+struct Candidate {
+    name: &'static str,
+    // None until smg has a route source for it
+    framework: Option<Framework>,
+    packages: &'static [&'static str],
+    markers: &'static [&'static str],
+}
+
+const CANDIDATES: [Candidate; 5] = [
+    Candidate {
+        name: "Next.js",
+        framework: Some(Framework::Next),
+        packages: &["next"],
+        markers: &[
+            "next.config.js",
+            "next.config.mjs",
+            "next.config.ts",
+            "next.config.cjs",
+        ],
+    },
+    Candidate {
+        name: "SvelteKit",
+        framework: Some(Framework::SvelteKit),
+        packages: &["@sveltejs/kit"],
+        markers: &["svelte.config.js", "svelte.config.ts"],
+    },
+    Candidate {
+        name: "Nuxt",
+        framework: Some(Framework::Nuxt),
+        packages: &["nuxt"],
+        markers: &["nuxt.config.ts", "nuxt.config.js"],
+    },
+    Candidate {
+        name: "Astro",
+        framework: None,
+        packages: &["astro"],
+        markers: &["astro.config.mjs", "astro.config.ts", "astro.config.js"],
+    },
+    Candidate {
+        name: "Remix",
+        framework: None,
+        packages: &["@remix-run/dev", "@remix-run/react"],
+        markers: &["remix.config.js"],
+    },
+];
+
+const LOCKFILES: [&str; 5] = [
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    "yarn.lock",
+    "bun.lock",
+    "npm-shrinkwrap.json",
+];
+
+pub struct Detection {
+    name: &'static str,
+    framework: Option<Framework>,
+    evidence: Vec<String>,
+}
+
+impl Detection {
+    pub fn framework(&self) -> Result<Framework> {
+        match self.framework {
+            Some(framework) => Ok(framework),
+            None => bail!(
+                "Detected {}, which smg can't scan yet. Set \"framework\" in smg.config.json to scan it anyway",
+                self.name
+            ),
+        }
+    }
+}
+
+// Picks the framework with the most evidence from package.json, lockfiles and config files
+pub fn detect(project_path: &Path) -> Result<Option<Detection>> {
+    let dependencies = package_dependencies(project_path)?;
+    let lockfiles: Vec<(&str, String)> = LOCKFILES
+        .iter()
+        .filter_map(|file| Some((*file, fs::read_to_string(project_path.join(file)).ok()?)))
+        .collect();
+
+    let mut best: Option<Detection> = None;
+    for candidate in &CANDIDATES {
+        let mut evidence = Vec::new();
+
+        for package in candidate.packages {
+            if let Some(section) = dependencies
+                .iter()
+                .find(|(_, name)| name == package)
+                .map(|(section, _)| section)
+            {
+                evidence.push(format!("{} in package.json {}", package, section));
+            }
+
+            let pattern = Regex::new(&format!(
+                r#"(?m)(?:^|[\s"'/]){0}@\S|"node_modules/{0}""#,
+                regex::escape(package)
+            ))
+            .expect("valid regex");
+            for (file, contents) in &lockfiles {
+                if pattern.is_match(contents) {
+                    evidence.push(format!("{} in {}", package, file));
+                }
+            }
+        }
+
+        for marker in candidate.markers {
+            if project_path.join(marker).is_file() {
+                evidence.push(marker.to_string());
+            }
+        }
+
+        let found_more = best
+            .as_ref()
+            .is_none_or(|best| evidence.len() > best.evidence.len());
+        if !evidence.is_empty() && found_more {
+            best = Some(Detection {
+                name: candidate.name,
+                framework: candidate.framework,
+                evidence,
+            });
+        }
+    }
+
+    if let Some(detection) = &best {
+        println!(
+            "Detected {} ({})",
+            detection.name,
+            detection.evidence.join(", ")
+        );
+    }
+    Ok(best)
+}
+
+// (section, package) for every dependency listed in package.json
+fn package_dependencies(project_path: &Path) -> Result<Vec<(&'static str, String)>> {
+    let path = project_path.join("package.json");
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+
+    let contents =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let package: Value = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    Ok(["dependencies", "devDependencies", "peerDependencies"]
+        .into_iter()
+        .filter_map(|section| Some((section, package.get(section)?.as_object()?)))
+        .flat_map(|(section, packages)| packages.keys().map(move |name| (section, name.clone())))
+        .collect())
+}
+
+// Folder the framework serves as-is, where sitemap.xml belongs by default
+pub fn static_dir(framework: Framework) -> &'static str {
+    match framework {
+        Framework::Next => "public",
+        Framework::SvelteKit => "static",
+        Framework::Nuxt => "public",
+    }
+}
@@ -0,0 +1,146 @@
+use anyhow::{Result, bail};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::{ParamKind, RouteInfo};
+
+// Values for the params of one concrete route
+#[derive(Debug, Clone, Default)]
+pub struct ParamValues {
+    pub params: HashMap<String, Vec<String>>,
+    pub last_modified: Option<DateTime<Utc>>,
+    pub label: Option<String>,
+}
+
+impl ParamValues {
+    // Reads params from a JSON object, e.g. { "slug": "a" } or { "slug": ["a", "b"] }
+    pub fn from_json(object: &Value) -> Option<Self> {
+        let object = object.as_object()?;
+        let mut params = HashMap::new();
+
+        for (name, value) in object {
+            params.insert(name.clone(), json_segments(value)?);
+        }
+
+        Some(Self {
+            params,
+            ..Self::default()
+        })
+    }
+}
+
+pub fn json_segments(value: &Value) -> Option<Vec<String>> {
+    match value {
+        Value::Array(items) => items.iter().map(json_scalar).collect(),
+        other => json_scalar(other).map(|segment| vec![segment]),
+    }
+}
+
+fn json_scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+// Turns a route with :param placeholders into one concrete route per set of values
+pub fn expand_route(route: &RouteInfo, values: &[ParamValues]) -> Result<Vec<RouteInfo>> {
+    let mut expanded: Vec<RouteInfo> = Vec::with_capacity(values.len());
+
+    for value in values {
+        let (concrete_route, display_route) = substitute(route, value)?;
+        if expanded
+            .iter()
+            .any(|existing| existing.route == concrete_route)
+        {
+            continue;
+        }
+
+        let mut entry = route.clone();
+        entry.label = value
+            .label
+            .clone()
+            .unwrap_or_else(|| crate::scanner::default_label_for_route(&display_route));
+        entry.href = concrete_route.clone();
+        entry.route = concrete_route;
+        entry
+            .params
+            .retain(|param| !value.params.contains_key(&param.name));
+        if value.last_modified.is_some() {
+            entry.last_modified = value.last_modified;
+        }
+        expanded.push(entry);
+    }
+
+    Ok(expanded)
+}
+
+// Returns the concrete route along with an unencoded version for labels.
+// Params without a value keep their placeholder, e.g. a [locale] segment above a [slug] page.
+fn substitute(route: &RouteInfo, value: &ParamValues) -> Result<(String, String)> {
+    let mut encoded = Vec::new();
+    let mut display = Vec::new();
+
+    for segment in route.route.split('/').filter(|s| !s.is_empty()) {
+        let param = route
+            .params
+            .iter()
+            .find(|param| param.to_segment() == segment);
+        let Some((param, parts)) =
+            param.and_then(|param| Some((param, value.params.get(&param.name)?)))
+        else {
+            encoded.push(segment.to_string());
+            display.push(segment.to_string());
+            continue;
+        };
+
+        let parts: Vec<&str> = parts
+            .iter()
+            .flat_map(|part| match param.kind {
+                ParamKind::Dynamic => vec![part.as_str()],
+                _ => part.split('/').filter(|p| !p.is_empty()).collect(),
+            })
+            .collect();
+
+        match param.kind {
+            ParamKind::Dynamic if parts.len() != 1 || parts[0].is_empty() => {
+                bail!(
+                    "Expected a single value for param '{}' of {}",
+                    param.name,
+                    route.route
+                )
+            }
+            ParamKind::CatchAll if parts.is_empty() => {
+                bail!(
+                    "Missing value for catch-all param '{}' of {}",
+                    param.name,
+                    route.route
+                )
+            }
+            _ => {
+                encoded.extend(parts.iter().map(|part| encode_segment(part)));
+                display.extend(parts.iter().map(|part| part.to_string()));
+            }
+        }
+    }
+
+    Ok((
+        format!("/{}", encoded.join("/")),
+        format!("/{}", display.join("/")),
+    ))
+}
+
+fn encode_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
@@ -0,0 +1,216 @@
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::Path;
+
+use crate::literal;
+use crate::scanner;
+use crate::{ParamKind, RouteParam};
+
+// Helpers shared by routers that use [param] folder and file names (SvelteKit, Nuxt)
+
+// One URL segment and the param it declares. An optional segment matches with and without it.
+pub struct Segment {
+    pub text: String,
+    pub param: Option<RouteParam>,
+    pub optional: bool,
+}
+
+impl Segment {
+    pub fn literal(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            param: None,
+            optional: false,
+        }
+    }
+
+    pub fn param(name: &str, kind: ParamKind, optional: bool) -> Self {
+        let param = RouteParam {
+            name: name.to_string(),
+            kind,
+        };
+        Self {
+            text: param.to_segment(),
+            param: Some(param),
+            optional,
+        }
+    }
+}
+
+// The first config file that exists, without comments, or an empty string when there is none
+pub fn read_config(project_path: &Path, files: &[&str]) -> Result<String> {
+    let Some(path) = files
+        .iter()
+        .map(|file| project_path.join(file))
+        .find(|path| path.is_file())
+    else {
+        return Ok(String::new());
+    };
+
+    let source =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(literal::strip_comments(&source))
+}
+
+// [slug], [[lang]], [...rest], with an optional matcher: [id=integer]
+pub fn parse_segment(name: &str) -> Result<Segment> {
+    let (inner, kind, optional) =
+        if let Some(inner) = name.strip_prefix("[[").and_then(|n| n.strip_suffix("]]")) {
+            (inner, ParamKind::Dynamic, true)
+        } else if let Some(inner) = name.strip_prefix("[...").and_then(|n| n.strip_suffix(']')) {
+            // Rest params also match zero segments
+            (inner, ParamKind::OptionalCatchAll, false)
+        } else if let Some(inner) = name.strip_prefix('[').and_then(|n| n.strip_suffix(']')) {
+            (inner, ParamKind::Dynamic, false)
+        } else if name.contains('[') {
+            bail!(
+                "Segments that mix text and params are not supported: {}",
+                name
+            );
+        } else {
+            return Ok(Segment::literal(name));
+        };
+
+    let param = inner.split_once('=').map_or(inner, |(param, _)| param);
+    if param.is_empty() || param.contains(['[', ']']) {
+        bail!(
+            "Segments that mix text and params are not supported: {}",
+            name
+        );
+    }
+
+    Ok(Segment::param(param, kind, optional))
+}
+
+// Every route a page's folder and file names match. (group) folders are not part of the URL.
+pub fn route_variants(
+    names: impl IntoIterator<Item = String>,
+) -> Result<Vec<(String, Vec<RouteParam>)>> {
+    let mut segments = Vec::new();
+
+    for name in names {
+        if scanner::is_group(&name) {
+            continue;
+        }
+
+        segments.push(parse_segment(&name)?);
+    }
+
+    Ok(variants(segments))
+}
+
+// Every route the segments match, with the params of each
+pub fn variants(segments: Vec<Segment>) -> Vec<(String, Vec<RouteParam>)> {
+    let mut variants = vec![(String::from("/"), Vec::new())];
+
+    for segment in segments {
+        let mut next = Vec::with_capacity(variants.len() * 2);
+        for (route, params) in variants {
+            if segment.optional {
+                next.push((route.clone(), params.clone()));
+            }
+
+            let mut route = route;
+            let mut params = params;
+            scanner::push_segment(&mut route, &segment.text);
+            params.extend(segment.param.clone());
+            next.push((route, params));
+        }
+        variants = next;
+    }
+
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn param(name: &str, kind: ParamKind) -> RouteParam {
+        RouteParam {
+            name: name.to_string(),
+            kind,
+        }
+    }
+
+    fn routes(names: &[&str]) -> Vec<(String, Vec<RouteParam>)> {
+        let segments = names.iter().map(|name| parse_segment(name).unwrap());
+        variants(segments.collect())
+    }
+
+    #[test]
+    fn parses_param_segments() {
+        let slug = parse_segment("[slug]").unwrap();
+        assert_eq!(slug.text, ":slug");
+        assert_eq!(slug.param, Some(param("slug", ParamKind::Dynamic)));
+        assert!(!slug.optional);
+
+        let lang = parse_segment("[[lang]]").unwrap();
+        assert_eq!(lang.text, ":lang");
+        assert!(lang.optional);
+
+        let rest = parse_segment("[...rest]").unwrap();
+        assert_eq!(rest.text, ":rest*");
+        assert_eq!(rest.param, Some(param("rest", ParamKind::OptionalCatchAll)));
+
+        // Matchers are not part of the param name
+        assert_eq!(parse_segment("[id=integer]").unwrap().text, ":id");
+
+        let about = parse_segment("about").unwrap();
+        assert_eq!(about.text, "about");
+        assert!(about.param.is_none());
+    }
+
+    #[test]
+    fn rejects_segments_that_mix_text_and_params() {
+        for name in ["post-[id]", "[a]-[b]", "[]", "[=integer]"] {
+            let error = parse_segment(name).err().unwrap();
+            assert!(
+                error.to_string().contains("mix text and params"),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn optional_segments_match_with_and_without_them() {
+        let variants = routes(&["[[lang]]", "docs", "[...path]"]);
+        assert_eq!(
+            variants,
+            [
+                (
+                    "/docs/:path*".to_string(),
+                    vec![param("path", ParamKind::OptionalCatchAll)]
+                ),
+                (
+                    "/:lang/docs/:path*".to_string(),
+                    vec![
+                        param("lang", ParamKind::Dynamic),
+                        param("path", ParamKind::OptionalCatchAll)
+                    ]
+                ),
+            ]
+        );
+
+        assert_eq!(routes(&[]), [("/".to_string(), Vec::new())]);
+        assert_eq!(routes(&["[[a]]", "[[b]]"]).len(), 4);
+    }
+
+    #[test]
+    fn skips_groups_in_route_variants() {
+        let names = ["(marketing)", "blog", "(posts)", "[slug]"].map(str::to_string);
+        assert_eq!(
+            route_variants(names).unwrap(),
+            [(
+                "/blog/:slug".to_string(),
+                vec![param("slug", ParamKind::Dynamic)]
+            )]
+        );
+
+        let names = ["[...slug]", "edit"].map(str::to_string);
+        assert_eq!(route_variants(names).unwrap()[0].0, "/:slug*/edit");
+
+        assert!(route_variants(["post-[id]".to_string()]).is_err());
+    }
+}
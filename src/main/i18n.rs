@@ -0,0 +1,162 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::config::I18nConfig;
+use crate::literal;
+use crate::next_config::NextConfig;
+use crate::{Alternate, RouteInfo};
+
+const NEXT_INTL_ROUTING_FILES: [&str; 6] = [
+    "src/i18n/routing.ts",
+    "src/i18n/routing.js",
+    "i18n/routing.ts",
+    "i18n/routing.js",
+    "src/i18n.ts",
+    "i18n.ts",
+];
+
+// Locales come from next.config i18n, then a next-intl routing file
+pub fn resolve(project_path: &Path, next_config: &NextConfig) -> Result<Option<I18nConfig>> {
+    if let Some(i18n) = &next_config.i18n {
+        return Ok(Some(i18n.clone()));
+    }
+
+    for file in NEXT_INTL_ROUTING_FILES {
+        let path = project_path.join(file);
+        if !path.is_file() {
+            continue;
+        }
+
+        let source = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let source = literal::strip_comments(&source);
+
+        let Some(locales) = literal::value_of(&source, "locales")
+            .as_ref()
+            .and_then(literal::as_string_list)
+        else {
+            continue;
+        };
+
+        let default_locale = literal::value_of(&source, "defaultLocale")
+            .and_then(|v| v.as_str().map(str::to_string));
+
+        // next-intl prefixes every locale unless localePrefix is "as-needed" or "never"
+        let prefix_mode = literal::value_of(&source, "localePrefix").and_then(|v| match v {
+            serde_json::Value::Object(prefix) => prefix
+                .get("mode")
+                .and_then(|m| m.as_str().map(str::to_string)),
+            other => other.as_str().map(str::to_string),
+        });
+        if prefix_mode.as_deref() == Some("never") {
+            return Ok(None);
+        }
+
+        return Ok(Some(I18nConfig {
+            locales,
+            default_locale,
+            param: "locale".to_string(),
+            prefix_default_locale: Some(prefix_mode.as_deref() != Some("as-needed")),
+        }));
+    }
+
+    Ok(None)
+}
+
+// Expands routes into one entry per locale, each listing all of its siblings as alternates.
+// Projects with a locale segment (app/[locale]/...) only expand routes under that segment,
+// otherwise every route is prefixed like Next's built-in i18n does.
+pub fn expand(routes: Vec<RouteInfo>, i18n: &I18nConfig) -> Vec<RouteInfo> {
+    let locale_segment = format!(":{}", i18n.param);
+    let segment_mode = routes
+        .iter()
+        .any(|route| route.params.iter().any(|param| param.name == i18n.param));
+
+    let mut expanded = Vec::new();
+    for route in routes {
+        let has_segment = route.params.iter().any(|param| param.name == i18n.param);
+        if segment_mode && !has_segment {
+            expanded.push(route);
+            continue;
+        }
+
+        let prefix_default = i18n.prefix_default_locale.unwrap_or(segment_mode);
+        let variants: Vec<(String, String)> = i18n
+            .locales
+            .iter()
+            .map(|locale| {
+                let unprefixed = !prefix_default && i18n.default_locale.as_ref() == Some(locale);
+                let localized = if has_segment {
+                    localize_segment(&route.route, &locale_segment, locale, unprefixed)
+                } else {
+                    localize_prefix(&route.route, locale, unprefixed)
+                };
+                (locale.clone(), localized)
+            })
+            .collect();
+
+        let mut alternates: Vec<Alternate> = variants
+            .iter()
+            .map(|(locale, localized)| Alternate {
+                hreflang: locale.clone(),
+                route: localized.clone(),
+                href: String::new(),
+            })
+            .collect();
+
+        if let Some((_, default_route)) = variants
+            .iter()
+            .find(|(locale, _)| i18n.default_locale.as_ref() == Some(locale))
+        {
+            alternates.push(Alternate {
+                hreflang: "x-default".to_string(),
+                route: default_route.clone(),
+                href: String::new(),
+            });
+        }
+
+        for (locale, localized) in variants {
+            let mut entry = route.clone();
+            entry.route = localized;
+            entry.params.retain(|param| param.name != i18n.param);
+            entry.locale = Some(locale);
+            entry.alternates = alternates.clone();
+            expanded.push(entry);
+        }
+    }
+
+    expanded
+}
+
+// Drops alternates whose URL is no longer in the sitemap, e.g. a locale excluded by a rule
+pub fn retain_alternates(routes: &mut [RouteInfo]) {
+    let kept: Vec<String> = routes.iter().map(|route| route.route.clone()).collect();
+    for route in routes.iter_mut() {
+        route
+            .alternates
+            .retain(|alternate| kept.contains(&alternate.route));
+    }
+}
+
+fn localize_segment(route: &str, locale_segment: &str, locale: &str, unprefixed: bool) -> String {
+    let segments: Vec<&str> = route
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .filter_map(|segment| match segment == locale_segment {
+            true if unprefixed => None,
+            true => Some(locale),
+            false => Some(segment),
+        })
+        .collect();
+
+    format!("/{}", segments.join("/"))
+}
+
+fn localize_prefix(route: &str, locale: &str, unprefixed: bool) -> String {
+    match (unprefixed, route) {
+        (true, _) => route.to_string(),
+        (false, "/") => format!("/{}", locale),
+        (false, _) => format!("/{}{}", locale, route),
+    }
+}
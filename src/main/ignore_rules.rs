@@ -0,0 +1,181 @@
+use ignore::Match;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+use walkdir::{DirEntry, WalkDir};
+
+use crate::route_source::Diagnostic;
+
+const IGNORE_FILE: &str = ".smgignore";
+
+// .gitignore rules from the repository root down to the scanned folders, plus .smgignore
+pub struct IgnoreRules {
+    respect_gitignore: bool,
+    project_path: PathBuf,
+    // Ordered from least to most specific, the last match decides
+    matchers: Vec<Gitignore>,
+    // Folders whose .gitignore has been loaded
+    gitignore_dirs: Vec<PathBuf>,
+    // Ignore files that could not be read, reported by the next walk
+    errors: Vec<Diagnostic>,
+}
+
+impl IgnoreRules {
+    pub fn load(project_path: &Path, respect_gitignore: bool) -> Self {
+        let project_path = absolute(project_path);
+        let mut rules = Self {
+            respect_gitignore,
+            project_path: project_path.clone(),
+            matchers: Vec::new(),
+            gitignore_dirs: Vec::new(),
+            errors: Vec::new(),
+        };
+
+        if respect_gitignore {
+            let repository_root = project_path
+                .ancestors()
+                .find(|dir| dir.join(".git").exists())
+                .unwrap_or(&project_path)
+                .to_path_buf();
+
+            rules.add(&repository_root, &repository_root.join(".git/info/exclude"));
+
+            // Every .gitignore between the repository root and the project applies to the project
+            let mut dirs: Vec<&Path> = project_path
+                .ancestors()
+                .take_while(|dir| dir.starts_with(&repository_root))
+                .collect();
+            dirs.reverse();
+            for dir in dirs {
+                rules.add_gitignore(dir);
+            }
+        }
+
+        // smg's own ignore file applies even when .gitignore files are not respected
+        rules.add(&project_path, &project_path.join(IGNORE_FILE));
+        rules
+    }
+
+    // Walks a directory without descending into ignored folders. Unreadable entries and ignore
+    // files are reported in diagnostics.
+    pub fn walk<'a>(
+        &'a mut self,
+        dir: &Path,
+        follow_symlinks: bool,
+        diagnostics: &'a mut Vec<Diagnostic>,
+    ) -> impl Iterator<Item = DirEntry> + 'a {
+        // .gitignore files between the project and the scanned folder: src/.gitignore for src/app
+        if self.respect_gitignore {
+            let mut dirs: Vec<PathBuf> = absolute(dir)
+                .ancestors()
+                .skip(1)
+                .take_while(|ancestor| {
+                    ancestor.starts_with(&self.project_path) && *ancestor != self.project_path
+                })
+                .map(Path::to_path_buf)
+                .collect();
+            dirs.reverse();
+            for dir in dirs {
+                self.add_gitignore(&dir);
+            }
+        }
+
+        diagnostics.append(&mut self.errors);
+
+        let mut entries = WalkDir::new(dir).follow_links(follow_symlinks).into_iter();
+        std::iter::from_fn(move || {
+            loop {
+                let entry = match entries.next()? {
+                    Ok(entry) => entry,
+                    Err(error) => {
+                        diagnostics.push(walk_diagnostic(error));
+                        continue;
+                    }
+                };
+
+                let path = absolute(entry.path());
+                let is_dir = entry.file_type().is_dir();
+                if self.is_ignored(&path, is_dir) {
+                    if is_dir {
+                        entries.skip_current_dir();
+                    }
+                    continue;
+                }
+
+                // Nested .gitignore files apply to everything below their folder
+                if is_dir && self.respect_gitignore {
+                    self.add_gitignore(&path);
+                    diagnostics.append(&mut self.errors);
+                }
+                return Some(entry);
+            }
+        })
+    }
+
+    fn add_gitignore(&mut self, dir: &Path) {
+        if self.gitignore_dirs.iter().any(|loaded| loaded == dir) {
+            return;
+        }
+        self.gitignore_dirs.push(dir.to_path_buf());
+        self.add(dir, &dir.join(".gitignore"));
+    }
+
+    fn add(&mut self, root: &Path, file: &Path) {
+        if !file.is_file() {
+            return;
+        }
+
+        let mut builder = GitignoreBuilder::new(root);
+        if let Some(error) = builder.add(file) {
+            self.errors.push(ignore_diagnostic(file, error));
+        }
+        match builder.build() {
+            Ok(matcher) => self.matchers.push(matcher),
+            Err(error) => self.errors.push(ignore_diagnostic(file, error)),
+        }
+    }
+
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        self.matchers
+            .iter()
+            .rev()
+            .filter(|matcher| path.starts_with(matcher.path()))
+            .find_map(|matcher| match matcher.matched(path, is_dir) {
+                Match::None => None,
+                Match::Ignore(_) => Some(true),
+                Match::Whitelist(_) => Some(false),
+            })
+            .unwrap_or(false)
+    }
+}
+
+// The ignore crate starts its messages with the file, which the diagnostic already names
+fn ignore_diagnostic(file: &Path, error: ignore::Error) -> Diagnostic {
+    let message = error.to_string();
+    let prefix = format!("{}: ", file.display());
+    Diagnostic::new(file, message.strip_prefix(&prefix).unwrap_or(&message))
+}
+
+// Loops and dangling links only come up when symlinks are followed
+fn walk_diagnostic(error: walkdir::Error) -> Diagnostic {
+    let path = error.path().unwrap_or(Path::new("")).to_path_buf();
+
+    let message = if let Some(ancestor) = error.loop_ancestor() {
+        format!("symlink loop back to {}", ancestor.display())
+    } else if error
+        .io_error()
+        .is_some_and(|io| io.kind() == std::io::ErrorKind::NotFound)
+    {
+        "dangling symlink".to_string()
+    } else {
+        error
+            .io_error()
+            .map(|io| io.to_string())
+            .unwrap_or_else(|| error.to_string())
+    };
+
+    Diagnostic { path, message }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,677 @@
+use regex::Regex;
+use serde_json::{Map, Number, Value};
+
+// Static reader for JavaScript literals (objects, arrays, strings, numbers, booleans, null).
+// Anything that needs evaluation (identifiers, calls, interpolation) is not a literal.
+// Object properties that are not literals are skipped so the literal ones can still be read.
+
+pub fn value_after(source: &str, pattern: &Regex) -> Option<Value> {
+    pattern
+        .find_iter(source)
+        .find_map(|found| parse(&source[found.end()..]).map(|(value, _)| value))
+}
+
+// Top-level settings of a config file's exported object, so that keys of the same name in
+// nested objects (a redirect's basePath, a preprocessor's extensions) are not mistaken for them
+pub struct ExportedConfig<'a> {
+    source: &'a str,
+    object: Option<Value>,
+}
+
+impl<'a> ExportedConfig<'a> {
+    pub fn parse(source: &'a str) -> Self {
+        Self {
+            source,
+            object: exported_object(source),
+        }
+    }
+
+    // Configs that can't be followed statically (e.g. a function that returns the config) fall
+    // back to the first property with the name
+    pub fn get(&self, key: &str) -> Option<Value> {
+        match &self.object {
+            Some(object) => object.get(key).cloned(),
+            None => value_of(self.source, key),
+        }
+    }
+}
+
+// The object a config file exports: `export default {...}` or `module.exports = {...}`, also
+// through wrappers like defineConfig({...}) or withMDX(config) and through a named const
+pub fn exported_object(source: &str) -> Option<Value> {
+    let export =
+        Regex::new(r"(?:\bexport\s+default|\bmodule\.exports\s*=)\s*").expect("valid regex");
+    let found = export.find(source)?;
+    object_expression(source, &source[found.end()..], 0)
+}
+
+fn object_expression(source: &str, expression: &str, depth: usize) -> Option<Value> {
+    // withNextIntl()(withMDX(config)) => config
+    let wrappers = Regex::new(r"^(?:[\w$.]+\s*(?:\(\s*\)\s*)*\(\s*)*").expect("valid regex");
+    let expression = &expression[wrappers.find(expression)?.end()..];
+
+    if expression.starts_with('{') {
+        return parse(expression)
+            .map(|(value, _)| value)
+            .filter(Value::is_object);
+    }
+
+    // export default config, declared as `const config: Config = {...}`
+    let name = Regex::new(r"^[\w$]+")
+        .expect("valid regex")
+        .find(expression)?;
+    if depth > 3 {
+        return None;
+    }
+    let declaration = Regex::new(&format!(
+        r"\b(?:const|let|var)\s+{}\b[^=]*=\s*",
+        regex::escape(name.as_str())
+    ))
+    .ok()?;
+    let found = declaration.find(source)?;
+    object_expression(source, &source[found.end()..], depth + 1)
+}
+
+// Literal value of an object property anywhere in the source, e.g. `basePath: '/docs'`
+pub fn value_of(source: &str, key: &str) -> Option<Value> {
+    let pattern = Regex::new(&format!(r#"\b{}['"]?\s*:"#, regex::escape(key))).ok()?;
+    value_after(source, &pattern)
+}
+
+// Literal returned by a named function, either `return <literal>` or an arrow `=> <literal>`
+pub fn function_return(source: &str, name: &str) -> Option<Value> {
+    let start = source.find(name)? + name.len();
+    let body = &source[start..];
+
+    if let Some(index) = function_arrow(body) {
+        let expression = body[index + 2..].trim_start();
+        if !expression.starts_with('{') {
+            let expression = expression.strip_prefix('(').unwrap_or(expression);
+            return parse(expression).map(|(value, _)| value);
+        }
+    }
+
+    let index = body.find("return")?;
+    parse(&body[index + "return".len()..]).map(|(value, _)| value)
+}
+
+// The function's own arrow is the first one outside brackets, so nested callbacks and
+// return types like `(): { slug: string }[] =>` are passed over. A `return` first means
+// the function has a block body.
+fn function_arrow(body: &str) -> Option<usize> {
+    let bytes = body.as_bytes();
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'=' if bytes.get(i + 1) == Some(&b'>') => {
+                if depth == 0 {
+                    return Some(i);
+                }
+                i += 1;
+            }
+            b'(' | b'[' | b'{' | b'<' => depth += 1,
+            b')' | b']' | b'}' | b'>' => depth = depth.saturating_sub(1),
+            b'r' if body[i..].starts_with("return") => return None,
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+pub fn parse(source: &str) -> Option<(Value, usize)> {
+    let mut parser = Parser {
+        source: source.as_bytes(),
+        pos: 0,
+    };
+    let value = parser.value()?;
+    Some((value, parser.pos))
+}
+
+// Removes comments so commented-out settings are not mistaken for live ones
+pub fn strip_comments(source: &str) -> String {
+    let bytes = source.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut quote: Option<u8> = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        match quote {
+            Some(q) => {
+                output.push(c);
+                if c == b'\\' && i + 1 < bytes.len() {
+                    output.push(bytes[i + 1]);
+                    i += 1;
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None if c == b'/' && bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            None if c == b'/' && bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i < bytes.len() && !bytes[i..].starts_with(b"*/") {
+                    i += 1;
+                }
+                i += 2;
+                continue;
+            }
+            None => {
+                if matches!(c, b'"' | b'\'' | b'`') {
+                    quote = Some(c);
+                }
+                output.push(c);
+            }
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&output).to_string()
+}
+
+pub fn as_string_list(value: &Value) -> Option<Vec<String>> {
+    match value {
+        Value::String(s) => Some(vec![s.clone()]),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| item.as_str().map(str::to_string))
+            .collect(),
+        _ => None,
+    }
+}
+
+struct Parser<'a> {
+    source: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.source.get(self.pos).copied()
+    }
+
+    fn skip_trivia(&mut self) {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_ascii_whitespace() => self.pos += 1,
+                Some(b'/') if self.source.get(self.pos + 1) == Some(&b'/') => {
+                    while let Some(c) = self.peek() {
+                        if c == b'\n' {
+                            break;
+                        }
+                        self.pos += 1;
+                    }
+                }
+                Some(b'/') if self.source.get(self.pos + 1) == Some(&b'*') => {
+                    self.pos += 2;
+                    while self.pos < self.source.len()
+                        && !self.source[self.pos..].starts_with(b"*/")
+                    {
+                        self.pos += 1;
+                    }
+                    self.pos = (self.pos + 2).min(self.source.len());
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn eat(&mut self, expected: u8) -> bool {
+        self.skip_trivia();
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value(&mut self) -> Option<Value> {
+        self.skip_trivia();
+        let value = match self.peek()? {
+            b'{' => self.object()?,
+            b'[' => self.array()?,
+            b'"' | b'\'' | b'`' => Value::String(self.string()?),
+            b'-' | b'0'..=b'9' | b'.' => self.number()?,
+            _ => match self.identifier()?.as_str() {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                "null" | "undefined" => Value::Null,
+                _ => return None,
+            },
+        };
+
+        // `as const`, `as Type` and `satisfies Type` do not change the value
+        self.skip_trivia();
+        let rest = &self.source[self.pos..];
+        for keyword in [&b"as"[..], b"satisfies"] {
+            if rest.starts_with(keyword)
+                && rest
+                    .get(keyword.len())
+                    .is_some_and(|c| c.is_ascii_whitespace())
+            {
+                self.pos += keyword.len();
+                self.skip_type();
+                break;
+            }
+        }
+
+        Some(value)
+    }
+
+    // Skips a type annotation up to the end of the current property or element
+    fn skip_type(&mut self) {
+        let mut depth = 0usize;
+
+        while let Some(c) = self.peek() {
+            match c {
+                b'=' if self.source.get(self.pos + 1) == Some(&b'>') => self.pos += 1,
+                b',' | b'}' | b']' | b')' | b';' if depth == 0 => return,
+                b'{' | b'[' | b'(' | b'<' => depth += 1,
+                b'}' | b']' | b')' | b'>' => depth = depth.saturating_sub(1),
+                b'"' | b'\'' | b'`' => {
+                    if self.skip_string().is_none() {
+                        return;
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn object(&mut self) -> Option<Value> {
+        self.pos += 1;
+        let mut map = Map::new();
+
+        loop {
+            if self.eat(b'}') {
+                return Some(Value::Object(map));
+            }
+
+            self.skip_trivia();
+            let start = self.pos;
+            match self.property() {
+                Some((key, value)) => {
+                    map.insert(key, value);
+                }
+                None => {
+                    self.pos = start;
+                    self.skip_expression()?;
+                }
+            }
+
+            if !self.eat(b',') {
+                return self.eat(b'}').then_some(Value::Object(map));
+            }
+        }
+    }
+
+    fn property(&mut self) -> Option<(String, Value)> {
+        let key = match self.peek()? {
+            b'"' | b'\'' => self.string()?,
+            b'0'..=b'9' => self.number()?.to_string(),
+            _ => self.identifier()?,
+        };
+
+        if !self.eat(b':') {
+            return None;
+        }
+
+        let value = self.value()?;
+        self.skip_trivia();
+        matches!(self.peek(), Some(b',' | b'}')).then_some((key, value))
+    }
+
+    // Skips to the end of the current property or element, keeping brackets and strings balanced
+    fn skip_expression(&mut self) -> Option<()> {
+        let start = self.pos;
+        let mut depth = 0usize;
+
+        loop {
+            self.skip_trivia();
+            match self.peek()? {
+                b',' | b'}' | b']' | b')' if depth == 0 => break,
+                b'{' | b'[' | b'(' => {
+                    depth += 1;
+                    self.pos += 1;
+                }
+                b'}' | b']' | b')' => {
+                    depth -= 1;
+                    self.pos += 1;
+                }
+                b'"' | b'\'' | b'`' => self.skip_string()?,
+                _ => self.pos += 1,
+            }
+        }
+
+        (self.pos > start).then_some(())
+    }
+
+    fn skip_string(&mut self) -> Option<()> {
+        let quote = self.peek()?;
+        self.pos += 1;
+
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            match c {
+                b'\\' => self.pos += 1,
+                c if c == quote => return Some(()),
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    fn array(&mut self) -> Option<Value> {
+        self.pos += 1;
+        let mut items = Vec::new();
+
+        loop {
+            if self.eat(b']') {
+                return Some(Value::Array(items));
+            }
+
+            items.push(self.value()?);
+
+            if !self.eat(b',') {
+                return self.eat(b']').then_some(Value::Array(items));
+            }
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let quote = self.peek()?;
+        self.pos += 1;
+        let mut bytes = Vec::new();
+
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            match c {
+                c if c == quote => return String::from_utf8(bytes).ok(),
+                b'$' if quote == b'`' && self.peek() == Some(b'{') => return None,
+                b'\\' => {
+                    let escaped = self.peek()?;
+                    self.pos += 1;
+                    match escaped {
+                        b'n' => bytes.push(b'\n'),
+                        b't' => bytes.push(b'\t'),
+                        b'r' => bytes.push(b'\r'),
+                        b'0' => bytes.push(0),
+                        b'x' | b'u' => {
+                            let c = self.code_point(escaped)?;
+                            bytes.extend(c.encode_utf8(&mut [0; 4]).as_bytes());
+                        }
+                        // Line continuation
+                        b'\n' => {}
+                        other => bytes.push(other),
+                    }
+                }
+                other => bytes.push(other),
+            }
+        }
+
+        None
+    }
+
+    // \xHH, \uHHHH and \u{H...} escapes, after the x or u
+    fn code_point(&mut self, kind: u8) -> Option<char> {
+        let rest = &self.source[self.pos..];
+        let (digits, len) = match kind {
+            b'x' => (rest.get(..2)?, 2),
+            _ if rest.first() == Some(&b'{') => {
+                let end = rest.iter().position(|c| *c == b'}')?;
+                (&rest[1..end], end + 1)
+            }
+            _ => (rest.get(..4)?, 4),
+        };
+
+        let code = u32::from_str_radix(std::str::from_utf8(digits).ok()?, 16).ok()?;
+        self.pos += len;
+        char::from_u32(code)
+    }
+
+    fn number(&mut self) -> Option<Value> {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_ascii_digit() || matches!(c, b'-' | b'+' | b'.' | b'e' | b'E' | b'_') {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+
+        let text = std::str::from_utf8(&self.source[start..self.pos])
+            .ok()?
+            .replace('_', "");
+        if let Ok(int) = text.parse::<i64>() {
+            return Some(Value::Number(int.into()));
+        }
+        text.parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map(Value::Number)
+    }
+
+    fn identifier(&mut self) -> Option<String> {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_ascii_alphanumeric() || c == b'_' || c == b'$' {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+
+        (self.pos > start)
+            .then(|| String::from_utf8_lossy(&self.source[start..self.pos]).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn value(source: &str) -> Option<Value> {
+        parse(source).map(|(value, _)| value)
+    }
+
+    #[test]
+    fn reads_strings_and_escapes() {
+        assert_eq!(value(r#""double""#), Some(json!("double")));
+        assert_eq!(value(r"'It\'s'"), Some(json!("It's")));
+        assert_eq!(value(r"'a\nb\tc\\d'"), Some(json!("a\nb\tc\\d")));
+        assert_eq!(value(r"'café \x41 \u{1F600}'"), Some(json!("café A 😀")));
+        assert_eq!(value("'unterminated"), None);
+    }
+
+    #[test]
+    fn reads_template_literals_without_interpolation() {
+        assert_eq!(value("`plain`"), Some(json!("plain")));
+        assert_eq!(value("`/blog/${slug}`"), None);
+
+        // An interpolated property is skipped, the others are still read
+        assert_eq!(
+            value("{ href: `/blog/${slug}`, title: 'Blog' }"),
+            Some(json!({ "title": "Blog" }))
+        );
+    }
+
+    #[test]
+    fn reads_numbers_and_keywords() {
+        assert_eq!(
+            value("[1, -2, 1_000, 0.5, true, false, null, undefined]"),
+            Some(json!([1, -2, 1000, 0.5, true, false, null, null]))
+        );
+        assert_eq!(value("someIdentifier"), None);
+    }
+
+    #[test]
+    fn ignores_type_assertions() {
+        assert_eq!(value("['md', 'mdx'] as const"), Some(json!(["md", "mdx"])));
+        assert_eq!(
+            value("{ locales: ['en', 'de'] as const, base: '/docs' as string }"),
+            Some(json!({ "locales": ["en", "de"], "base": "/docs" }))
+        );
+        assert_eq!(
+            value("{ paths: { base: '/app' } satisfies Record<string, string>, trailing: true }"),
+            Some(json!({ "paths": { "base": "/app" }, "trailing": true }))
+        );
+        assert_eq!(
+            value("{ basePath: '/docs' } satisfies NextConfig"),
+            Some(json!({ "basePath": "/docs" }))
+        );
+    }
+
+    #[test]
+    fn skips_shorthand_spread_and_computed_properties() {
+        assert_eq!(
+            value("{ ...base, slug, [key]: 1, 'quoted-key': 2, 3: 'three', nested: { ok: true } }"),
+            Some(json!({ "quoted-key": 2, "3": "three", "nested": { "ok": true } }))
+        );
+        assert_eq!(
+            value("{ run: () => { return [1, 2] }, fn() { return 1 }, keep: 'yes' }"),
+            Some(json!({ "keep": "yes" }))
+        );
+        assert_eq!(value("[1, someVariable]"), None);
+    }
+
+    #[test]
+    fn skips_comments() {
+        let source =
+            "{\n  // basePath: '/old',\n  /* trailing: true, */ basePath: '/new', // done\n}";
+        assert_eq!(value(source), Some(json!({ "basePath": "/new" })));
+        assert_eq!(
+            strip_comments("a // gone\nb /* gone */ c 'not // a comment'"),
+            "a \nb  c 'not // a comment'"
+        );
+    }
+
+    #[test]
+    fn finds_value_after_pattern() {
+        let pattern = Regex::new(r"\bbasePath\s*:").unwrap();
+        let source = "const basePath: string = env; export default { basePath: '/docs' }";
+        assert_eq!(value_after(source, &pattern), Some(json!("/docs")));
+    }
+
+    #[test]
+    fn finds_property_values() {
+        let source = "export default { kit: { paths: { base: '/app' } }, 'srcDir': 'src/' }";
+        assert_eq!(value_of(source, "base"), Some(json!("/app")));
+        assert_eq!(value_of(source, "srcDir"), Some(json!("src/")));
+        assert_eq!(value_of(source, "missing"), None);
+    }
+
+    #[test]
+    fn reads_top_level_settings_of_the_exported_config() {
+        let svelte = "const mdsvexOptions = { extensions: ['.md'] };\n\
+            const config = {\n\
+              extensions: ['.svelte', '.md'],\n\
+              preprocess: [mdsvex(mdsvexOptions)]\n\
+            };\n\
+            export default config;";
+        let config = ExportedConfig::parse(svelte);
+        assert_eq!(config.get("extensions"), Some(json!([".svelte", ".md"])));
+        assert_eq!(config.get("kit"), None);
+
+        // A config built by a function falls back to the first property with the name
+        let function = "module.exports = (phase) => ({ basePath: '/docs' })";
+        assert_eq!(
+            ExportedConfig::parse(function).get("basePath"),
+            Some(json!("/docs"))
+        );
+    }
+
+    #[test]
+    fn finds_exported_objects() {
+        assert_eq!(
+            exported_object("module.exports = { basePath: '/docs' }"),
+            Some(json!({ "basePath": "/docs" }))
+        );
+        assert_eq!(
+            exported_object("export default defineNuxtConfig({ srcDir: 'src/' })"),
+            Some(json!({ "srcDir": "src/" }))
+        );
+
+        let named = "const redirects = { basePath: false }\n\
+            const nextConfig: NextConfig = { basePath: '/docs' }\n\
+            export default withNextIntl()(withMDX(nextConfig))";
+        assert_eq!(exported_object(named), Some(json!({ "basePath": "/docs" })));
+
+        assert_eq!(exported_object("export default (phase) => ({})"), None);
+        assert_eq!(exported_object("const config = {}"), None);
+    }
+
+    #[test]
+    fn reads_arrow_function_results() {
+        assert_eq!(
+            function_return("export const entries = () => [{ slug: 'a' }]", "entries"),
+            Some(json!([{ "slug": "a" }]))
+        );
+        assert_eq!(
+            function_return("export const entries = () => ({ slug: 'a' })", "entries"),
+            Some(json!({ "slug": "a" }))
+        );
+        assert_eq!(
+            function_return(
+                "export const entries = async () => { return [{ slug: 'b' }] }",
+                "entries"
+            ),
+            Some(json!([{ "slug": "b" }]))
+        );
+    }
+
+    #[test]
+    fn reads_return_statements() {
+        assert_eq!(
+            function_return(
+                "export async function generateStaticParams() {\n  const unused = items.map((item) => item.slug)\n  return [{ slug: 'a' }, { slug: 'b' }]\n}",
+                "generateStaticParams"
+            ),
+            Some(json!([{ "slug": "a" }, { "slug": "b" }]))
+        );
+        assert_eq!(
+            function_return(
+                "async redirects() { return posts.map(toRule) }",
+                "redirects"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn passes_over_return_type_annotations() {
+        assert_eq!(
+            function_return(
+                "export function generateStaticParams(): Promise<{ slug: string }[]> {\n  return [{ slug: 'a' }]\n}",
+                "generateStaticParams"
+            ),
+            Some(json!([{ "slug": "a" }]))
+        );
+        assert_eq!(
+            function_return(
+                "export const entries = (): { slug: string }[] => [{ slug: 'a' }]",
+                "entries"
+            ),
+            Some(json!([{ "slug": "a" }]))
+        );
+        assert_eq!(
+            function_return(
+                "export const entries: EntryGenerator = (): Array<{ id: number }> => {\n  return [{ id: 1 }]\n}",
+                "entries"
+            ),
+            Some(json!([{ "id": 1 }]))
+        );
+    }
+}
@@ -6,16 +6,126 @@ use std::path::PathBuf;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RouteInfo {
     pub route: String,
+    #[serde(default)]
+    pub href: String,
     pub path: String,
     pub label: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub title: String,
     pub description: String,
     pub last_modified: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<RouteParam>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternates: Vec<Alternate>,
+    // Why the route is kept out of sitemap.xml, if it declares noindex
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub noindex: Option<String>,
+    // The middleware matcher that runs on this route, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guarded_by: Option<String>,
+    #[serde(default, skip_serializing_if = "EntryKind::is_page")]
+    pub entry: EntryKind,
+    // HTTP methods exported by a route handler
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub methods: Vec<String>,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    #[default]
+    Page,
+    RouteHandler,
+    MetadataFile,
+}
+
+impl EntryKind {
+    pub fn is_page(&self) -> bool {
+        *self == EntryKind::Page
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alternate {
+    pub hreflang: String,
+    pub route: String,
+    #[serde(default)]
+    pub href: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteParam {
+    pub name: String,
+    pub kind: ParamKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamKind {
+    Dynamic,
+    CatchAll,
+    OptionalCatchAll,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteKind {
+    Static,
+    Dynamic,
+    CatchAll,
+    OptionalCatchAll,
+}
+
+impl RouteInfo {
+    // The most general param decides the kind, e.g. /:lang/docs/:slug* is an optional catch-all
+    pub fn kind(&self) -> RouteKind {
+        self.params
+            .iter()
+            .map(|param| match param.kind {
+                ParamKind::Dynamic => RouteKind::Dynamic,
+                ParamKind::CatchAll => RouteKind::CatchAll,
+                ParamKind::OptionalCatchAll => RouteKind::OptionalCatchAll,
+            })
+            .max_by_key(|kind| *kind as u8)
+            .unwrap_or(RouteKind::Static)
+    }
+}
+
+impl RouteParam {
+    // Route syntax for the param: :id, :slug+ (catch-all) or :slug* (optional catch-all)
+    pub fn to_segment(&self) -> String {
+        match self.kind {
+            ParamKind::Dynamic => format!(":{}", self.name),
+            ParamKind::CatchAll => format!(":{}+", self.name),
+            ParamKind::OptionalCatchAll => format!(":{}*", self.name),
+        }
+    }
+}
+
+mod detect;
+mod expand;
+mod file_router;
+mod ignore_rules;
+mod literal;
+mod middleware;
+mod next_config;
+mod next_source;
+mod nuxt;
+mod page_metadata;
+mod path_pattern;
+mod providers;
+mod redirects;
+mod route_source;
 mod scanner;
 mod sitemap_xml;
 mod sitemap_json;
+mod sveltekit;
 mod config;
+mod i18n;
+mod urls;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -24,7 +134,7 @@ struct Args {
     #[arg(short, long)]
     project: Option<String>,
 
-    /// Output path for sitemap.xml
+    /// Output path for sitemap.xml (default: the framework's static folder, e.g. public/)
     #[arg(long)]
     xml_output: Option<String>,
 
@@ -35,6 +145,10 @@ struct Args {
     /// Base URL for sitemap
     #[arg(short, long)]
     base_url: Option<String>,
+
+    /// Fail when any file or folder had to be skipped while scanning
+    #[arg(long)]
+    strict: bool,
 }
 
 fn main() -> Result<()> {
@@ -46,33 +160,109 @@ fn main() -> Result<()> {
     
     // Merge config with command line args (CLI args take precedence)
     let project_path = args.project.unwrap_or(config.project.clone());
-    let xml_output = args.xml_output.unwrap_or(config.xml_output.clone());
-    let json_output = args.json_output.unwrap_or(config.json_output.clone());
     let base_url = args.base_url.unwrap_or(config.base_url.clone());
-    
-    // Convert to PathBuf where needed
     let project_path = PathBuf::from(project_path);
-    let xml_output_path = PathBuf::from(xml_output);
-    let json_output_path = PathBuf::from(json_output);
     
+    // Detect the framework unless it is configured
+    let framework = match config.framework {
+        Some(framework) => framework,
+        None => match detect::detect(&project_path).context("Failed to detect framework")? {
+            Some(detection) => detection.framework()?,
+            None => {
+                println!("No framework detected, scanning as Next.js");
+                config::Framework::default()
+            }
+        },
+    };
+
+    // sitemap.xml goes in the folder the framework serves as-is, unless configured
+    let static_dir = project_path.join(detect::static_dir(framework));
+    let output_dir = if static_dir.is_dir() { static_dir } else { project_path.clone() };
+    let xml_output_path = args.xml_output.or(config.xml_output.clone())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| output_dir.join("sitemap.xml"));
+    let json_output_path = args.json_output.or(config.json_output.clone())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| project_path.join("sitemap.json"));
+    
+    // Pick the route source for the project's framework
+    let source = route_source::load(framework, &project_path)?;
+
     // Scan project for all routes
-    let all_routes = scanner::scan_project(&project_path)
+    let scan = source
+        .scan(&project_path, &config.scanner)
         .context("Failed to scan project")?;
+
+    // Report entries the scanner had to skip
+    for diagnostic in &scan.diagnostics {
+        if args.strict {
+            eprintln!("Error: {}", diagnostic);
+        } else {
+            eprintln!("Warning: {}", diagnostic);
+        }
+    }
+    if args.strict && !scan.diagnostics.is_empty() {
+        anyhow::bail!("Scan skipped {} entries in strict mode", scan.diagnostics.len());
+    }
+    let all_routes = scan.routes;
+
+    // Expand dynamic routes from their configured data sources
+    let all_routes = providers::expand_dynamic_routes(all_routes, &config.dynamic_params)?;
+
+    // Apply the framework's routing rules, e.g. redirects, rewrites and middleware
+    let mut all_routes = source.resolve(&project_path, all_routes)?;
+
+    // Apply basePath, trailingSlash and static export settings to route URLs
+    let url_options = source.url_options().with_overrides(&config.urls);
+    url_options.apply(&mut all_routes);
+
+    // Resolve locales for sitemap.xml
+    let i18n = match &config.i18n {
+        Some(i18n) => Some(i18n.clone()),
+        None => source.locales(&project_path).context("Failed to resolve locales")?,
+    };
     
+    // Report pages that opt out of indexing
+    for route in &all_routes {
+        if let Some(reason) = &route.noindex {
+            println!("Excluding {} from sitemap.xml: {}", route.route, reason);
+        } else if let Some(matcher) = &route.guarded_by
+            && config.exclude_guarded_routes
+        {
+            println!(
+                "Excluding {} from sitemap.xml: guarded by middleware matcher {}",
+                route.route, matcher
+            );
+        }
+    }
+
     // Filter routes for main sitemaps
     let mut main_json_routes: Vec<RouteInfo> = all_routes.iter()
-        .filter(|route| config.include_in_main_json(&route.route))
+        .filter(|route| config.include_in_main_json(route))
         .cloned()
         .collect();
     
     let mut main_xml_routes: Vec<RouteInfo> = all_routes.iter()
-        .filter(|route| config.include_in_main_xml(&route.route))
+        .filter(|route| config.include_in_main_xml(route))
         .cloned()
         .collect();
     
     // Sort routes for consistent output
     main_json_routes.sort_by(|a, b| a.route.cmp(&b.route));
     main_xml_routes.sort_by(|a, b| a.route.cmp(&b.route));
+
+    // Expand locales into concrete URLs with hreflang alternates. Rules see both forms, so
+    // /about drops every locale and /de/about only the German one.
+    if let Some(i18n) = &i18n {
+        main_xml_routes = i18n::expand(main_xml_routes, i18n);
+        main_xml_routes.retain(|route| config.include_in_main_xml(route));
+        i18n::retain_alternates(&mut main_xml_routes);
+        url_options.apply(&mut main_xml_routes);
+    }
+
+    // Handle dynamic routes that were never expanded into concrete URLs
+    let main_xml_routes = config.unresolved_routes.xml.apply(main_xml_routes, "sitemap.xml")?;
+    let main_json_routes = config.unresolved_routes.json.apply(main_json_routes, "sitemap.json")?;
     
     // Generate main sitemap.xml
     sitemap_xml::generate(&main_xml_routes, &xml_output_path, &base_url)
@@ -90,12 +280,17 @@ fn main() -> Result<()> {
         // Filter routes for this custom sitemap
         let custom_routes: Vec<RouteInfo> = all_routes.iter()
             .filter(|route| {
-                let matching_sitemaps = config.get_matching_custom_sitemaps(&route.route);
+                let matching_sitemaps = config.get_matching_custom_sitemaps(route);
                 matching_sitemaps.contains(key)
             })
             .cloned()
             .collect();
         
+        let custom_routes = config
+            .unresolved_routes
+            .json
+            .apply(custom_routes, &custom_config.output)?;
+
         if !custom_routes.is_empty() {
             // Sort custom routes
             let mut sorted_custom_routes = custom_routes.clone();
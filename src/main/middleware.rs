@@ -0,0 +1,83 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use crate::RouteInfo;
+use crate::literal;
+use crate::path_pattern::PathPattern;
+
+const MIDDLEWARE_FILES: [&str; 4] = [
+    "middleware.ts",
+    "middleware.js",
+    "src/middleware.ts",
+    "src/middleware.js",
+];
+
+// Tags routes matched by the literal `config.matcher` of middleware.{ts,js} as guarded
+pub fn tag_guarded(project_path: &Path, routes: &mut [RouteInfo]) -> Result<()> {
+    let Some(path) = MIDDLEWARE_FILES
+        .iter()
+        .map(|file| project_path.join(file))
+        .find(|path| path.is_file())
+    else {
+        return Ok(());
+    };
+
+    let source =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let source = literal::strip_comments(&source);
+
+    // Without a matcher, middleware runs on every request and guards nothing in particular
+    let Some(matcher) = literal::value_of(&source, "matcher") else {
+        eprintln!(
+            "Warning: {} has no literal config.matcher, routes are not tagged as guarded",
+            path.display()
+        );
+        return Ok(());
+    };
+
+    let matchers: Vec<(PathPattern, String)> = matcher_sources(&matcher)
+        .into_iter()
+        .filter_map(|source| match PathPattern::parse(&source) {
+            Some(pattern) => Some((pattern, source)),
+            None => {
+                eprintln!(
+                    "Warning: Unsupported middleware matcher '{}' in {}",
+                    source,
+                    path.display()
+                );
+                None
+            }
+        })
+        .collect();
+
+    for route in routes {
+        route.guarded_by = matchers
+            .iter()
+            .find(|(pattern, _)| pattern.is_match(&route.route))
+            .map(|(_, source)| source.clone());
+    }
+
+    Ok(())
+}
+
+// Matchers are a string, or an array of strings and { source, has, missing } objects.
+// A `has` condition depends on the request, so those matchers are left out.
+fn matcher_sources(matcher: &Value) -> Vec<String> {
+    let items = match matcher {
+        Value::Array(items) => items.iter().collect(),
+        other => vec![other],
+    };
+
+    items
+        .into_iter()
+        .filter_map(|item| match item {
+            Value::String(source) => Some(source.clone()),
+            Value::Object(object) if !object.contains_key("has") => {
+                Some(object.get("source")?.as_str()?.to_string())
+            }
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,162 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use crate::config::I18nConfig;
+use crate::literal;
+
+const CONFIG_FILES: [&str; 4] = [
+    "next.config.js",
+    "next.config.mjs",
+    "next.config.ts",
+    "next.config.cjs",
+];
+
+pub const DEFAULT_PAGE_EXTENSIONS: [&str; 6] = ["tsx", "ts", "jsx", "js", "mdx", "md"];
+
+// Settings read statically from next.config; values that are not literals are left unset
+#[derive(Debug, Clone, Default)]
+pub struct NextConfig {
+    pub page_extensions: Option<Vec<String>>,
+    pub base_path: Option<String>,
+    pub trailing_slash: Option<bool>,
+    pub static_export: Option<bool>,
+    pub i18n: Option<I18nConfig>,
+    pub redirects: Vec<Redirect>,
+    pub rewrites: Vec<Rewrite>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Redirect {
+    pub source: String,
+    pub destination: String,
+    pub permanent: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Rewrite {
+    pub source: String,
+    pub destination: String,
+}
+
+impl NextConfig {
+    pub fn load(project_path: &Path) -> Result<Self> {
+        let Some(path) = CONFIG_FILES
+            .iter()
+            .map(|file| project_path.join(file))
+            .find(|path| path.is_file())
+        else {
+            return Ok(Self::default());
+        };
+
+        let source = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        Ok(Self::parse(&literal::strip_comments(&source)))
+    }
+
+    fn parse(source: &str) -> Self {
+        let config = literal::ExportedConfig::parse(source);
+        let setting = |key: &str| config.get(key);
+
+        let page_extensions = setting("pageExtensions")
+            .as_ref()
+            .and_then(literal::as_string_list);
+
+        let base_path = setting("basePath").and_then(|v| v.as_str().map(str::to_string));
+        let trailing_slash = setting("trailingSlash").and_then(|v| v.as_bool());
+        let static_export =
+            setting("output").and_then(|v| v.as_str().map(|output| output == "export"));
+
+        // Built-in i18n (Pages Router) never prefixes the default locale
+        let i18n = setting("i18n").and_then(|i18n| {
+            Some(I18nConfig {
+                locales: literal::as_string_list(i18n.get("locales")?)?,
+                default_locale: i18n
+                    .get("defaultLocale")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string),
+                param: "locale".to_string(),
+                prefix_default_locale: Some(false),
+            })
+        });
+
+        let redirects = route_rules(source, "redirects")
+            .into_iter()
+            .filter_map(|rule| {
+                Some(Redirect {
+                    source: rule.get("source")?.as_str()?.to_string(),
+                    destination: rule.get("destination")?.as_str()?.to_string(),
+                    permanent: rule
+                        .get("permanent")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false),
+                })
+            })
+            .collect();
+
+        let rewrites = route_rules(source, "rewrites")
+            .into_iter()
+            .filter_map(|rule| {
+                Some(Rewrite {
+                    source: rule.get("source")?.as_str()?.to_string(),
+                    destination: rule.get("destination")?.as_str()?.to_string(),
+                })
+            })
+            .collect();
+
+        Self {
+            page_extensions,
+            base_path,
+            trailing_slash,
+            static_export,
+            i18n,
+            redirects,
+            rewrites,
+        }
+    }
+
+    pub fn page_extensions(&self) -> Vec<String> {
+        match &self.page_extensions {
+            Some(extensions) => extensions.clone(),
+            None => DEFAULT_PAGE_EXTENSIONS
+                .iter()
+                .map(|ext| ext.to_string())
+                .collect(),
+        }
+    }
+}
+
+// Rules returned by redirects() or rewrites(). Rewrites can also be grouped into
+// { beforeFiles, afterFiles, fallback }. Rules that only apply to some requests
+// (has / missing) or that opt out of basePath are left out.
+fn route_rules(source: &str, name: &str) -> Vec<Value> {
+    let pattern = Regex::new(&format!(r"\b{}\s*(?:\(|:|=)", name)).expect("valid regex");
+    let Some(found) = pattern.find(source) else {
+        return Vec::new();
+    };
+    let Some(value) = literal::function_return(&source[found.start()..], name) else {
+        return Vec::new();
+    };
+
+    let rules = match value {
+        Value::Array(rules) => rules,
+        Value::Object(groups) => ["beforeFiles", "afterFiles", "fallback"]
+            .iter()
+            .filter_map(|group| groups.get(*group)?.as_array().cloned())
+            .flatten()
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    rules
+        .into_iter()
+        .filter(|rule| {
+            rule.get("has").is_none()
+                && rule.get("missing").is_none()
+                && rule.get("basePath") != Some(&Value::Bool(false))
+        })
+        .collect()
+}
@@ -0,0 +1,51 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::RouteInfo;
+use crate::config::{I18nConfig, ScannerConfig};
+use crate::i18n;
+use crate::middleware;
+use crate::next_config::NextConfig;
+use crate::redirects;
+use crate::route_source::{RouteSource, ScanResult};
+use crate::scanner;
+use crate::urls::UrlOptions;
+
+// Next.js App Router and Pages Router projects
+pub struct NextSource {
+    next_config: NextConfig,
+}
+
+impl NextSource {
+    pub fn load(project_path: &Path) -> Result<Self> {
+        let next_config = NextConfig::load(project_path).context("Failed to read next.config")?;
+        Ok(Self { next_config })
+    }
+}
+
+impl RouteSource for NextSource {
+    fn scan(&self, project_path: &Path, options: &ScannerConfig) -> Result<ScanResult> {
+        scanner::scan_project(project_path, options, &self.next_config.page_extensions())
+    }
+
+    fn resolve(&self, project_path: &Path, routes: Vec<RouteInfo>) -> Result<Vec<RouteInfo>> {
+        // Add rewrite aliases and drop redirected routes
+        let mut routes = redirects::apply(routes, &self.next_config);
+
+        // Tag routes that run behind middleware
+        middleware::tag_guarded(project_path, &mut routes).context("Failed to read middleware")?;
+        Ok(routes)
+    }
+
+    fn url_options(&self) -> UrlOptions {
+        UrlOptions::new(
+            self.next_config.base_path.as_deref(),
+            self.next_config.trailing_slash.unwrap_or(false),
+            self.next_config.static_export.unwrap_or(false),
+        )
+    }
+
+    fn locales(&self, project_path: &Path) -> Result<Option<I18nConfig>> {
+        i18n::resolve(project_path, &self.next_config)
+    }
+}
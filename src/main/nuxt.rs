@@ -0,0 +1,241 @@
+use anyhow::{Result, bail};
+use regex::Regex;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::ScannerConfig;
+use crate::file_router::{self, Segment};
+use crate::ignore_rules::IgnoreRules;
+use crate::literal;
+use crate::route_source::{Diagnostic, RouteSource, ScanResult};
+use crate::scanner;
+use crate::urls::UrlOptions;
+use crate::{ParamKind, RouteInfo, RouteParam};
+
+const CONFIG_FILES: [&str; 2] = ["nuxt.config.ts", "nuxt.config.js"];
+const PAGE_EXTENSIONS: [&str; 6] = ["vue", "ts", "tsx", "js", "jsx", "mjs"];
+
+// Nuxt projects: every file in pages/ (app/pages/ in Nuxt 4) is a page
+pub struct NuxtSource {
+    pages_dir: PathBuf,
+    url_options: UrlOptions,
+}
+
+impl NuxtSource {
+    pub fn load(project_path: &Path) -> Result<Self> {
+        let source = file_router::read_config(project_path, &CONFIG_FILES)?;
+        let config = literal::ExportedConfig::parse(&source);
+
+        // app.baseURL
+        let base_path = config
+            .get("app")
+            .and_then(|app| app.get("baseURL")?.as_str().map(str::to_string));
+
+        let src_dir = config
+            .get("srcDir")
+            .and_then(|dir| dir.as_str().map(str::to_string));
+        let pages_dir = match src_dir {
+            Some(src_dir) => project_path.join(src_dir).join("pages"),
+            None if project_path.join("app/pages").is_dir() => project_path.join("app/pages"),
+            None => project_path.join("pages"),
+        };
+
+        Ok(Self {
+            pages_dir,
+            url_options: UrlOptions::new(base_path.as_deref(), false, false),
+        })
+    }
+}
+
+impl RouteSource for NuxtSource {
+    fn scan(&self, project_path: &Path, options: &ScannerConfig) -> Result<ScanResult> {
+        if !self.pages_dir.is_dir() {
+            bail!("Could not find {} in project", self.pages_dir.display());
+        }
+
+        let mut ignores = IgnoreRules::load(project_path, options.respect_gitignore);
+        let mut diagnostics = Vec::new();
+        let mut skipped = Vec::new();
+        let mut routes: Vec<RouteInfo> = Vec::new();
+
+        for entry in ignores.walk(&self.pages_dir, options.follow_symlinks, &mut skipped) {
+            let path = entry.path();
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let Some(stem) = page_stem(path) else {
+                continue;
+            };
+
+            match page_routes(project_path, &self.pages_dir, path, stem) {
+                Ok(infos) => {
+                    for info in infos {
+                        if !routes.iter().any(|existing| existing.route == info.route) {
+                            routes.push(info);
+                        }
+                    }
+                }
+                Err(error) => diagnostics.push(Diagnostic::new(path, format!("{:#}", error))),
+            }
+        }
+        diagnostics.extend(skipped);
+
+        Ok(ScanResult {
+            routes: scanner::with_optional_catch_all_parents(routes),
+            diagnostics,
+        })
+    }
+
+    fn url_options(&self) -> UrlOptions {
+        self.url_options.clone()
+    }
+}
+
+fn page_stem(path: &Path) -> Option<&str> {
+    let file_name = path.file_name()?.to_str()?;
+    let (stem, ext) = file_name.rsplit_once('.')?;
+    (PAGE_EXTENSIONS.contains(&ext) && !stem.is_empty()).then_some(stem)
+}
+
+fn page_routes(
+    project_path: &Path,
+    pages_dir: &Path,
+    path: &Path,
+    stem: &str,
+) -> Result<Vec<RouteInfo>> {
+    let meta = page_meta(path);
+
+    // definePageMeta({ path }) replaces the route from the file name
+    let variants = match meta.as_ref().and_then(|meta| meta.get("path")?.as_str()) {
+        Some(custom) => custom_path_variants(custom)?,
+        None => {
+            let relative = path.parent().unwrap_or(path).strip_prefix(pages_dir)?;
+            let folders = relative
+                .components()
+                .filter_map(|component| match component {
+                    std::path::Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+                    _ => None,
+                });
+            // index files map to their folder
+            let file = (stem != "index").then(|| stem.to_string());
+            file_router::route_variants(folders.chain(file))?
+        }
+    };
+
+    // A custom title or name makes a better label than the route
+    let label = meta.as_ref().and_then(|meta| {
+        meta.get("title")
+            .and_then(|title| title.as_str().map(str::to_string))
+            .or_else(|| {
+                let name = meta.get("name")?.as_str()?;
+                Some(scanner::default_label_for_route(&format!("/{}", name)))
+            })
+    });
+
+    variants
+        .into_iter()
+        .map(|(route, params)| {
+            let mut info = scanner::file_route_info(project_path, path, route, params)?;
+            if let Some(label) = &label {
+                info.label = label.clone();
+            }
+            Ok(info)
+        })
+        .collect()
+}
+
+fn page_meta(path: &Path) -> Option<Value> {
+    let source = literal::strip_comments(&fs::read_to_string(path).ok()?);
+    let pattern = Regex::new(r"\bdefinePageMeta\s*\(").expect("valid regex");
+    literal::value_after(&source, &pattern)
+}
+
+// Vue Router paths: /posts/:id, /:lang?/docs, /files/:path(.*)*, /shop/:slug+
+fn custom_path_variants(path: &str) -> Result<Vec<(String, Vec<RouteParam>)>> {
+    let param_pattern = Regex::new(r"^:(\w+)(?:\([^/]*\))?([?*+]?)$").expect("valid regex");
+    let mut segments = Vec::new();
+
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        if !segment.contains(':') {
+            segments.push(Segment::literal(segment));
+            continue;
+        }
+
+        let Some(captures) = param_pattern.captures(segment) else {
+            bail!("Unsupported segment '{}' in definePageMeta path", segment);
+        };
+        let (kind, optional) = match &captures[2] {
+            "?" => (ParamKind::Dynamic, true),
+            "*" => (ParamKind::OptionalCatchAll, false),
+            "+" => (ParamKind::CatchAll, false),
+            _ => (ParamKind::Dynamic, false),
+        };
+        segments.push(Segment::param(&captures[1], kind, optional));
+    }
+
+    Ok(file_router::variants(segments))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn routes(path: &str) -> Vec<String> {
+        custom_path_variants(path)
+            .unwrap()
+            .into_iter()
+            .map(|(route, _)| route)
+            .collect()
+    }
+
+    #[test]
+    fn maps_vue_router_paths() {
+        assert_eq!(routes("/posts/:id"), ["/posts/:id"]);
+        assert_eq!(routes("/:lang?/docs"), ["/docs", "/:lang/docs"]);
+        assert_eq!(routes("/files/:path(.*)*"), ["/files/:path*"]);
+        assert_eq!(routes("/shop/:slug+"), ["/shop/:slug+"]);
+        assert_eq!(routes("/"), ["/"]);
+
+        let (_, params) = custom_path_variants("/user/:id(\\d+)").unwrap().remove(0);
+        assert_eq!(
+            params,
+            [RouteParam {
+                name: "id".to_string(),
+                kind: ParamKind::Dynamic,
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_vue_router_segments() {
+        assert!(custom_path_variants("/post-:id").is_err());
+        assert!(custom_path_variants("/:a-:b").is_err());
+    }
+
+    #[test]
+    fn adds_the_parent_of_a_mid_route_catch_all() {
+        let names = ["[...slug]", "edit"].map(str::to_string);
+        let routes: Vec<RouteInfo> = file_router::route_variants(names)
+            .unwrap()
+            .into_iter()
+            .map(|(route, params)| {
+                serde_json::from_value(serde_json::json!({
+                    "route": route,
+                    "href": route,
+                    "path": "pages/[...slug]/edit.vue",
+                    "label": "",
+                    "description": "",
+                    "last_modified": null,
+                    "params": params,
+                }))
+                .unwrap()
+            })
+            .collect();
+
+        let routes = scanner::with_optional_catch_all_parents(routes);
+        let routes: Vec<&str> = routes.iter().map(|route| route.route.as_str()).collect();
+        assert_eq!(routes, ["/:slug*/edit", "/edit"]);
+    }
+}
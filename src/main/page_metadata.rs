@@ -0,0 +1,143 @@
+use regex::Regex;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::literal;
+
+// Static `export const metadata = {...}` or a literal returned by generateMetadata
+#[derive(Debug, Clone, Default)]
+pub struct PageMetadata {
+    pub path: PathBuf,
+    pub title: Option<Title>,
+    pub description: Option<String>,
+    pub index: Option<bool>,
+}
+
+// title: "About" or title: { default, template, absolute }
+#[derive(Debug, Clone, Default)]
+pub struct Title {
+    pub plain: Option<String>,
+    pub default: Option<String>,
+    pub template: Option<String>,
+    pub absolute: Option<String>,
+}
+
+impl Title {
+    fn from_json(value: &Value) -> Option<Self> {
+        if let Some(plain) = value.as_str() {
+            return Some(Self {
+                plain: Some(plain.to_string()),
+                ..Self::default()
+            });
+        }
+
+        let field = |name: &str| value.get(name)?.as_str().map(str::to_string);
+        let title = Self {
+            plain: None,
+            default: field("default"),
+            template: field("template"),
+            absolute: field("absolute"),
+        };
+
+        (title.default.is_some() || title.template.is_some() || title.absolute.is_some())
+            .then_some(title)
+    }
+
+    // The title as written by the page, before any layout template is applied
+    pub fn label(&self) -> Option<&str> {
+        self.plain
+            .as_deref()
+            .or(self.absolute.as_deref())
+            .or(self.default.as_deref())
+    }
+}
+
+pub fn read(path: &Path) -> Option<PageMetadata> {
+    let source = literal::strip_comments(&fs::read_to_string(path).ok()?);
+
+    let metadata = Regex::new(r"export\s+const\s+metadata\b[^=]*=")
+        .ok()
+        .and_then(|pattern| literal::value_after(&source, &pattern))
+        .or_else(|| literal::function_return(&source, "generateMetadata"))?;
+
+    if !metadata.is_object() {
+        return None;
+    }
+
+    Some(PageMetadata {
+        path: path.to_path_buf(),
+        index: metadata.get("robots").and_then(robots_index),
+        title: metadata.get("title").and_then(Title::from_json),
+        description: metadata
+            .get("description")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+    })
+}
+
+// robots: { index: false } or robots: "noindex, nofollow"
+fn robots_index(robots: &Value) -> Option<bool> {
+    if let Some(directives) = robots.as_str() {
+        let directives: Vec<String> = directives
+            .split(',')
+            .map(|directive| directive.trim().to_lowercase())
+            .collect();
+        if directives.iter().any(|d| d == "noindex" || d == "none") {
+            return Some(false);
+        }
+        return directives.iter().any(|d| d == "index").then_some(true);
+    }
+
+    robots.get("index")?.as_bool()
+}
+
+// The page itself or the closest layout that sets robots.index decides, returning the
+// file that declared noindex
+pub fn noindex_source<'a>(layouts: &'a [PageMetadata], page: &'a PageMetadata) -> Option<&'a Path> {
+    std::iter::once(page)
+        .chain(layouts.iter().rev())
+        .find_map(|metadata| metadata.index.map(|index| (index, metadata.path.as_path())))
+        .and_then(|(index, path)| (!index).then_some(path))
+}
+
+// Resolves the full document title the way Next does: a layout's title.template wraps the
+// titles of the segments below it, title.absolute ignores templates, and a page without a
+// title inherits the closest layout title (title.default for object titles)
+pub fn document_title(layouts: &[PageMetadata], page: &PageMetadata) -> Option<String> {
+    let mut inherited: Option<String> = None;
+    let mut template: Option<&str> = None;
+
+    for layout in layouts {
+        let Some(title) = &layout.title else {
+            continue;
+        };
+
+        if let Some(resolved) = title.resolve(template) {
+            inherited = Some(resolved);
+        }
+        if let Some(layout_template) = title.template.as_deref() {
+            template = Some(layout_template);
+        }
+    }
+
+    page.title
+        .as_ref()
+        .and_then(|title| title.resolve(template))
+        .or(inherited)
+}
+
+impl Title {
+    fn resolve(&self, template: Option<&str>) -> Option<String> {
+        if let Some(absolute) = &self.absolute {
+            return Some(absolute.clone());
+        }
+
+        // title.default goes through the parent template like a plain title
+        let title = self.plain.as_ref().or(self.default.as_ref())?;
+        Some(match template {
+            Some(template) => template.replace("%s", title),
+            None => title.clone(),
+        })
+    }
+}
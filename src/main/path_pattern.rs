@@ -0,0 +1,331 @@
+use fancy_regex::Regex;
+use std::collections::HashMap;
+
+// Path patterns as used by next.config redirects, rewrites and middleware matchers:
+// /blog/:slug, /docs/:path*, /shop/:path+, /post/:id?, /user/:id(\d+), /(.*).
+// Custom patterns are JavaScript regexes, so lookarounds like /((?!api).*) are supported.
+#[derive(Debug, Clone)]
+pub struct PathPattern {
+    tokens: Vec<Token>,
+    regex: Regex,
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    Literal(String),
+    Param {
+        name: String,
+        // Custom regex from :name(...) or an unnamed (...) group
+        pattern: Option<String>,
+        prefix: String,
+        modifier: Option<char>,
+    },
+}
+
+impl PathPattern {
+    pub fn parse(pattern: &str) -> Option<Self> {
+        let tokens = tokenize(pattern)?;
+        let mut regex = String::from("^");
+        let mut groups = 0;
+
+        for token in &tokens {
+            match token {
+                Token::Literal(text) => regex.push_str(&fancy_regex::escape(text)),
+                Token::Param {
+                    pattern,
+                    prefix,
+                    modifier,
+                    ..
+                } => {
+                    let value = match pattern {
+                        Some(pattern) => format!("(?:{})", pattern),
+                        None => "[^/]+".to_string(),
+                    };
+                    let prefix = fancy_regex::escape(prefix);
+                    let group = format!("?P<g{}>", groups);
+                    groups += 1;
+                    let repeated = format!("{value}(?:{prefix}{value})*");
+                    regex.push_str(&match modifier {
+                        None => format!("{prefix}({group}{value})"),
+                        Some('?') => format!("(?:{prefix}({group}{value}))?"),
+                        Some('+') => format!("{prefix}({group}{repeated})"),
+                        _ => format!("(?:{prefix}({group}{repeated}))?"),
+                    });
+                }
+            }
+        }
+        regex.push_str("/?$");
+
+        Some(Self {
+            tokens,
+            regex: Regex::new(&regex).ok()?,
+        })
+    }
+
+    pub fn is_match(&self, path: &str) -> bool {
+        self.regex.is_match(path).unwrap_or(false)
+    }
+
+    // Param values by name; unnamed groups are numbered from 0
+    pub fn captures(&self, path: &str) -> Option<HashMap<String, String>> {
+        let captures = self.regex.captures(path).ok()??;
+        let mut values = HashMap::new();
+
+        for (index, name) in self.param_names().enumerate() {
+            if let Some(value) = captures.name(&format!("g{}", index)) {
+                values.insert(name.to_string(), value.as_str().to_string());
+            }
+        }
+
+        Some(values)
+    }
+
+    // Builds a path from param values, or None if a required param has no value
+    pub fn fill(&self, values: &HashMap<String, String>) -> Option<String> {
+        let mut path = String::new();
+
+        for token in &self.tokens {
+            match token {
+                Token::Literal(text) => path.push_str(text),
+                Token::Param {
+                    name,
+                    prefix,
+                    modifier,
+                    ..
+                } => match values.get(name).filter(|value| !value.is_empty()) {
+                    Some(value) => {
+                        path.push_str(prefix);
+                        path.push_str(value);
+                    }
+                    None if matches!(modifier, Some('?' | '*')) => {}
+                    None => return None,
+                },
+            }
+        }
+
+        if path.is_empty() {
+            path.push('/');
+        }
+        Some(path)
+    }
+
+    fn param_names(&self) -> impl Iterator<Item = &str> {
+        self.tokens.iter().filter_map(|token| match token {
+            Token::Param { name, .. } => Some(name.as_str()),
+            Token::Literal(_) => None,
+        })
+    }
+}
+
+fn tokenize(pattern: &str) -> Option<Vec<Token>> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut tokens = Vec::new();
+    let mut literal = String::new();
+    let mut unnamed = 0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let (name, custom) = match c {
+            ':' => {
+                let start = i + 1;
+                i = start;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                if i == start {
+                    return None;
+                }
+                let name: String = chars[start..i].iter().collect();
+                let custom = if chars.get(i) == Some(&'(') {
+                    Some(group(&chars, &mut i)?)
+                } else {
+                    None
+                };
+                (name, custom)
+            }
+            '(' => {
+                let custom = group(&chars, &mut i)?;
+                unnamed += 1;
+                ((unnamed - 1).to_string(), Some(custom))
+            }
+            '\\' => {
+                literal.extend(chars.get(i + 1));
+                i += 2;
+                continue;
+            }
+            '{' | '}' => return None,
+            _ => {
+                literal.push(c);
+                i += 1;
+                continue;
+            }
+        };
+
+        let modifier = chars
+            .get(i)
+            .copied()
+            .filter(|c| matches!(c, '?' | '*' | '+'));
+        if modifier.is_some() {
+            i += 1;
+        }
+
+        // A slash before the param belongs to it, so /docs/:path* also matches /docs
+        let prefix = if literal.ends_with('/') {
+            literal.pop();
+            "/".to_string()
+        } else {
+            String::new()
+        };
+        if !literal.is_empty() {
+            tokens.push(Token::Literal(std::mem::take(&mut literal)));
+        }
+
+        tokens.push(Token::Param {
+            name,
+            pattern: custom,
+            prefix,
+            modifier,
+        });
+    }
+
+    if !literal.is_empty() {
+        tokens.push(Token::Literal(literal));
+    }
+    Some(tokens)
+}
+
+// A balanced (...) group starting at chars[*i], returning its contents
+fn group(chars: &[char], i: &mut usize) -> Option<String> {
+    let start = *i + 1;
+    let mut depth = 0;
+
+    while *i < chars.len() {
+        match chars[*i] {
+            '\\' => *i += 1,
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    *i += 1;
+                    return Some(chars[start..*i - 1].iter().collect());
+                }
+            }
+            _ => {}
+        }
+        *i += 1;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(source: &str) -> PathPattern {
+        PathPattern::parse(source).unwrap()
+    }
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn matches_named_params() {
+        let blog = pattern("/blog/:slug");
+        assert!(blog.is_match("/blog/hello"));
+        assert!(blog.is_match("/blog/hello/"));
+        assert!(!blog.is_match("/blog"));
+        assert!(!blog.is_match("/blog/a/b"));
+        assert_eq!(
+            blog.captures("/blog/hello"),
+            Some(values(&[("slug", "hello")]))
+        );
+    }
+
+    #[test]
+    fn zero_or_more_matches_its_parent() {
+        let docs = pattern("/docs/:path*");
+        assert!(docs.is_match("/docs"));
+        assert!(docs.is_match("/docs/a"));
+        assert!(!docs.is_match("/documentation"));
+        assert_eq!(docs.captures("/docs/a/b"), Some(values(&[("path", "a/b")])));
+        assert_eq!(docs.captures("/docs"), Some(values(&[])));
+
+        assert_eq!(docs.fill(&values(&[])), Some("/docs".to_string()));
+        assert_eq!(
+            docs.fill(&values(&[("path", "a/b")])),
+            Some("/docs/a/b".to_string())
+        );
+    }
+
+    #[test]
+    fn one_or_more_needs_a_segment() {
+        let shop = pattern("/shop/:path+");
+        assert!(!shop.is_match("/shop"));
+        assert!(shop.is_match("/shop/a/b"));
+        assert_eq!(shop.fill(&values(&[])), None);
+    }
+
+    #[test]
+    fn optional_params() {
+        let post = pattern("/post/:id?");
+        assert!(post.is_match("/post"));
+        assert!(post.is_match("/post/1"));
+        assert!(!post.is_match("/post/1/2"));
+        assert_eq!(post.fill(&values(&[])), Some("/post".to_string()));
+        assert_eq!(
+            post.fill(&values(&[("id", "1")])),
+            Some("/post/1".to_string())
+        );
+    }
+
+    #[test]
+    fn custom_param_patterns() {
+        let user = pattern(r"/user/:id(\d+)");
+        assert!(user.is_match("/user/42"));
+        assert!(!user.is_match("/user/abc"));
+        assert_eq!(user.captures("/user/42"), Some(values(&[("id", "42")])));
+    }
+
+    #[test]
+    fn unnamed_groups_are_numbered() {
+        let all = pattern("/(.*)");
+        assert!(all.is_match("/"));
+        assert_eq!(all.captures("/a/b"), Some(values(&[("0", "a/b")])));
+
+        let two = pattern("/(en|de)/(.*)");
+        assert_eq!(
+            two.captures("/de/about"),
+            Some(values(&[("0", "de"), ("1", "about")]))
+        );
+    }
+
+    #[test]
+    fn supports_lookarounds_in_middleware_matchers() {
+        let matcher = pattern("/((?!api|_next/static|favicon.ico).*)");
+        assert!(matcher.is_match("/about"));
+        assert!(!matcher.is_match("/api/users"));
+        assert!(!matcher.is_match("/_next/static/chunk.js"));
+    }
+
+    #[test]
+    fn fill_requires_required_params() {
+        let blog = pattern("/blog/:slug");
+        assert_eq!(blog.fill(&values(&[])), None);
+        assert_eq!(blog.fill(&values(&[("slug", "")])), None);
+        assert_eq!(pattern("/").fill(&values(&[])), Some("/".to_string()));
+    }
+
+    #[test]
+    fn escapes_and_unsupported_syntax() {
+        assert!(pattern(r"/a\:b").is_match("/a:b"));
+        assert!(PathPattern::parse("/blog{/:slug}?").is_none());
+        assert!(PathPattern::parse("/blog/:").is_none());
+        assert!(PathPattern::parse("/blog/(unclosed").is_none());
+    }
+}
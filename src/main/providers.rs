@@ -0,0 +1,539 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::process::{Command, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::RouteInfo;
+use crate::config::{DynamicParamSource, ParamProvider};
+use crate::expand::{self, ParamValues};
+
+type Record = Map<String, Value>;
+
+// Larger timestamps are in milliseconds; as seconds they would be past the year 5000
+const MILLISECOND_TIMESTAMPS: i64 = 100_000_000_000;
+
+// Replaces every route that has a configured param source with its concrete routes
+pub fn expand_dynamic_routes(
+    routes: Vec<RouteInfo>,
+    sources: &HashMap<String, DynamicParamSource>,
+) -> Result<Vec<RouteInfo>> {
+    for route in sources.keys() {
+        if !routes.iter().any(|existing| &existing.route == route) {
+            eprintln!(
+                "Warning: dynamic_params route '{}' does not match any scanned route",
+                route
+            );
+        }
+    }
+
+    let mut expanded = Vec::with_capacity(routes.len());
+    for route in routes {
+        let Some(source) = sources.get(&route.route) else {
+            expanded.push(route);
+            continue;
+        };
+
+        let concrete = expand_with_source(&route, source)
+            .with_context(|| format!("Failed to expand dynamic route '{}'", route.route))?;
+        expanded.extend(concrete);
+    }
+
+    Ok(expanded)
+}
+
+fn expand_with_source(route: &RouteInfo, source: &DynamicParamSource) -> Result<Vec<RouteInfo>> {
+    let records = load_records(&source.provider)?;
+
+    let values = records
+        .iter()
+        .enumerate()
+        .map(|(index, record)| {
+            param_values(route, source, record)
+                .with_context(|| format!("Invalid record {}", index + 1))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    expand::expand_route(route, &values)
+}
+
+fn load_records(provider: &ParamProvider) -> Result<Vec<Record>> {
+    match provider {
+        ParamProvider::Json { file, pointer } => {
+            let reader = BufReader::new(
+                File::open(file).with_context(|| format!("Failed to open {}", file))?,
+            );
+            let document: Value = serde_json::from_reader(reader)
+                .with_context(|| format!("Failed to parse {}", file))?;
+
+            let records = match pointer {
+                Some(pointer) => document
+                    .pointer(pointer)
+                    .with_context(|| format!("No value at '{}' in {}", pointer, file))?,
+                None => &document,
+            };
+
+            records
+                .as_array()
+                .with_context(|| format!("Expected an array of objects in {}", file))?
+                .iter()
+                .map(|record| {
+                    record
+                        .as_object()
+                        .cloned()
+                        .with_context(|| format!("Expected an array of objects in {}", file))
+                })
+                .collect()
+        }
+        ParamProvider::Csv { file } => {
+            let mut reader =
+                csv::Reader::from_path(file).with_context(|| format!("Failed to open {}", file))?;
+            let headers = reader
+                .headers()
+                .with_context(|| format!("Failed to read headers of {}", file))?
+                .clone();
+
+            reader
+                .records()
+                .map(|row| {
+                    let row = row.with_context(|| format!("Failed to read {}", file))?;
+                    Ok(headers
+                        .iter()
+                        .zip(row.iter())
+                        .map(|(header, value)| {
+                            (header.to_string(), Value::String(value.to_string()))
+                        })
+                        .collect())
+                })
+                .collect()
+        }
+        ParamProvider::Sqlite { database, query } => {
+            let connection =
+                Connection::open_with_flags(database, OpenFlags::SQLITE_OPEN_READ_ONLY)
+                    .with_context(|| format!("Failed to open {}", database))?;
+            let mut statement = connection
+                .prepare(query)
+                .with_context(|| format!("Failed to prepare query on {}", database))?;
+            let columns: Vec<String> = statement
+                .column_names()
+                .iter()
+                .map(|name| name.to_string())
+                .collect();
+
+            let rows = statement.query_map([], |row| {
+                let mut record = Record::new();
+                for (index, column) in columns.iter().enumerate() {
+                    record.insert(column.clone(), sqlite_value(row.get_ref(index)?));
+                }
+                Ok(record)
+            })?;
+
+            rows.collect::<rusqlite::Result<Vec<_>>>()
+                .with_context(|| format!("Failed to run query on {}", database))
+        }
+        ParamProvider::Command {
+            command,
+            args,
+            timeout_secs,
+        } => {
+            let stdout = run_command(command, args, Duration::from_secs(*timeout_secs))
+                .with_context(|| format!("Command '{}' failed", command))?;
+
+            // One JSON object per line
+            stdout
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(index, line)| match serde_json::from_str::<Value>(line) {
+                    Ok(Value::Object(record)) => Ok(record),
+                    _ => anyhow::bail!(
+                        "Line {} of the output of '{}' is not a JSON object",
+                        index + 1,
+                        command
+                    ),
+                })
+                .collect()
+        }
+    }
+}
+
+fn run_command(command: &str, args: &[String], timeout: Duration) -> Result<String> {
+    let mut process = if args.is_empty() {
+        shell_command(command)
+    } else {
+        let mut process = Command::new(command);
+        process.args(args);
+        process
+    };
+
+    let mut child = process
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to start command")?;
+
+    // Drain the pipes on their own threads so a chatty command cannot block on a full pipe
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            anyhow::bail!("Timed out after {} seconds", timeout.as_secs());
+        }
+        thread::sleep(Duration::from_millis(20));
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    if !status.success() {
+        anyhow::bail!("Exited with {}: {}", status, stderr.trim());
+    }
+
+    Ok(stdout)
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut process = Command::new("cmd");
+    process.args(["/C", command]);
+    process
+}
+
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> Command {
+    let mut process = Command::new("sh");
+    process.args(["-c", command]);
+    process
+}
+
+fn read_in_background<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut output);
+        }
+        output
+    })
+}
+
+fn sqlite_value(value: ValueRef) -> Value {
+    match value {
+        ValueRef::Integer(int) => Value::from(int),
+        ValueRef::Real(real) => Value::from(real),
+        ValueRef::Text(text) => Value::String(String::from_utf8_lossy(text).to_string()),
+        ValueRef::Null | ValueRef::Blob(_) => Value::Null,
+    }
+}
+
+fn param_values(
+    route: &RouteInfo,
+    source: &DynamicParamSource,
+    record: &Record,
+) -> Result<ParamValues> {
+    let mut values = ParamValues::default();
+
+    for param in &route.params {
+        // Params that are not mapped and not in the record stay placeholders
+        let mapped = source.params.get(&param.name);
+        let field = mapped.unwrap_or(&param.name);
+        let Some(value) = record.get(field) else {
+            if mapped.is_some() {
+                anyhow::bail!("Missing field '{}' for param '{}'", field, param.name);
+            }
+            continue;
+        };
+        let segments = expand::json_segments(value)
+            .with_context(|| format!("Field '{}' is not a string, number or list", field))?;
+        values.params.insert(param.name.clone(), segments);
+    }
+
+    if values.params.is_empty() {
+        let names: Vec<&str> = route.params.iter().map(|p| p.name.as_str()).collect();
+        anyhow::bail!("No fields found for params {}", names.join(", "));
+    }
+
+    if let Some(field) = &source.lastmod
+        && let Some(value) = record.get(field)
+    {
+        values.last_modified = lastmod_value(value)
+            .with_context(|| format!("Invalid date {} in field '{}'", value, field))?;
+    }
+
+    if let Some(field) = &source.label {
+        values.label = match record.get(field) {
+            None | Some(Value::Null) => None,
+            Some(Value::String(label)) => Some(label.clone()),
+            Some(Value::Number(label)) => Some(label.to_string()),
+            Some(value) => anyhow::bail!("Label {} in field '{}' is not text", value, field),
+        };
+    }
+
+    Ok(values)
+}
+
+// Date strings, or Unix timestamps in seconds (or milliseconds) as stored in INTEGER columns
+fn lastmod_value(value: &Value) -> Result<Option<DateTime<Utc>>> {
+    match value {
+        Value::Null => Ok(None),
+        Value::String(text) if text.is_empty() => Ok(None),
+        Value::String(text) => parse_lastmod(text).map(Some),
+        Value::Number(number) => {
+            let timestamp = number
+                .as_i64()
+                .context("Expected a whole number of seconds since the Unix epoch")?;
+            let datetime = if timestamp.abs() >= MILLISECOND_TIMESTAMPS {
+                DateTime::from_timestamp_millis(timestamp)
+            } else {
+                DateTime::from_timestamp(timestamp, 0)
+            };
+            datetime.context("Timestamp is out of range").map(Some)
+        }
+        _ => anyhow::bail!("Expected a date string or a Unix timestamp"),
+    }
+}
+
+// Accepts RFC 3339 timestamps, "YYYY-MM-DD HH:MM:SS" and plain dates
+fn parse_lastmod(value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Ok(datetime.with_timezone(&Utc));
+    }
+
+    if let Ok(datetime) = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S") {
+        return Ok(datetime.and_utc());
+    }
+
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")?;
+    Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EntryKind, ParamKind, RouteParam};
+    use serde_json::json;
+
+    const DATABASE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/params.db");
+    const JSON_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/params.json");
+    const CSV_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/params.csv");
+
+    fn blog_route() -> RouteInfo {
+        RouteInfo {
+            route: "/blog/:slug".to_string(),
+            href: String::new(),
+            path: "app/blog/[slug]/page.tsx".to_string(),
+            label: "Slug Detail".to_string(),
+            title: String::new(),
+            description: String::new(),
+            last_modified: None,
+            params: vec![RouteParam {
+                name: "slug".to_string(),
+                kind: ParamKind::Dynamic,
+            }],
+            locale: None,
+            alternates: Vec::new(),
+            noindex: None,
+            guarded_by: None,
+            entry: EntryKind::Page,
+            methods: Vec::new(),
+        }
+    }
+
+    fn source(mut provider: Value, extra: Value) -> DynamicParamSource {
+        provider
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(provider).unwrap()
+    }
+
+    fn sqlite_source(query: &str, extra: Value) -> DynamicParamSource {
+        source(
+            json!({ "type": "sqlite", "database": DATABASE, "query": query }),
+            extra,
+        )
+    }
+
+    fn date(value: &str) -> Option<DateTime<Utc>> {
+        Some(
+            DateTime::parse_from_rfc3339(value)
+                .unwrap()
+                .with_timezone(&Utc),
+        )
+    }
+
+    #[test]
+    fn expands_params_from_sqlite() {
+        let source = sqlite_source(
+            "SELECT slug, title, updated_at FROM posts ORDER BY slug",
+            json!({ "lastmod": "updated_at", "label": "title" }),
+        );
+        let routes = expand_with_source(&blog_route(), &source).unwrap();
+
+        let paths: Vec<&str> = routes.iter().map(|route| route.route.as_str()).collect();
+        assert_eq!(paths, ["/blog/hello-world", "/blog/second-post"]);
+        assert!(routes.iter().all(|route| route.params.is_empty()));
+
+        // INTEGER epoch columns are timestamps, NULL keeps the file's date and the default label
+        assert_eq!(routes[0].last_modified, date("2023-11-14T22:13:20Z"));
+        assert_eq!(routes[0].label, "Hello World");
+        assert_eq!(routes[1].last_modified, None);
+        assert_eq!(routes[1].label, "Second Post");
+    }
+
+    #[test]
+    fn maps_fields_and_reads_text_dates_and_numeric_labels() {
+        let source = sqlite_source(
+            "SELECT slug AS post, published, views FROM posts ORDER BY slug",
+            json!({ "params": { "slug": "post" }, "lastmod": "published", "label": "views" }),
+        );
+        let routes = expand_with_source(&blog_route(), &source).unwrap();
+
+        assert_eq!(routes[0].route, "/blog/hello-world");
+        assert_eq!(routes[0].last_modified, date("2023-11-14T00:00:00Z"));
+        assert_eq!(routes[0].label, "120");
+        assert_eq!(routes[1].last_modified, date("2024-02-01T08:30:00Z"));
+        assert_eq!(routes[1].label, "7");
+    }
+
+    #[test]
+    fn reads_millisecond_timestamps() {
+        let source = sqlite_source(
+            "SELECT slug, updated_at * 1000 AS updated_at FROM posts WHERE updated_at IS NOT NULL",
+            json!({ "lastmod": "updated_at" }),
+        );
+        let routes = expand_with_source(&blog_route(), &source).unwrap();
+        assert_eq!(routes[0].last_modified, date("2023-11-14T22:13:20Z"));
+    }
+
+    #[test]
+    fn rejects_unreadable_lastmod() {
+        let source = sqlite_source(
+            "SELECT slug, 1.5 AS updated_at FROM posts",
+            json!({ "lastmod": "updated_at" }),
+        );
+        let error = expand_with_source(&blog_route(), &source).unwrap_err();
+        assert!(
+            format!("{:#}", error).contains("Invalid date 1.5 in field 'updated_at'"),
+            "{:#}",
+            error
+        );
+    }
+
+    #[test]
+    fn rejects_missing_mapped_field() {
+        let source = sqlite_source(
+            "SELECT title FROM posts",
+            json!({ "params": { "slug": "post" } }),
+        );
+        let error = expand_with_source(&blog_route(), &source).unwrap_err();
+        assert!(format!("{:#}", error).contains("Missing field 'post' for param 'slug'"));
+    }
+
+    #[test]
+    fn expands_params_from_json_at_a_pointer() {
+        let source = source(
+            json!({ "type": "json", "file": JSON_FILE, "pointer": "/data/posts" }),
+            json!({ "lastmod": "updated", "label": "title" }),
+        );
+        let routes = expand_with_source(&blog_route(), &source).unwrap();
+
+        let paths: Vec<&str> = routes.iter().map(|route| route.route.as_str()).collect();
+        assert_eq!(paths, ["/blog/hello-world", "/blog/second-post"]);
+        assert_eq!(routes[0].last_modified, date("2023-11-14T22:13:20Z"));
+        assert_eq!(routes[0].label, "Hello World");
+        assert_eq!(routes[1].last_modified, date("2024-02-01T08:30:00Z"));
+    }
+
+    #[test]
+    fn rejects_json_without_records_at_the_pointer() {
+        let missing = source(
+            json!({ "type": "json", "file": JSON_FILE, "pointer": "/data/pages" }),
+            json!({}),
+        );
+        let error = expand_with_source(&blog_route(), &missing).unwrap_err();
+        assert!(format!("{:#}", error).contains("No value at '/data/pages'"));
+
+        let not_an_array = source(json!({ "type": "json", "file": JSON_FILE }), json!({}));
+        let error = expand_with_source(&blog_route(), &not_an_array).unwrap_err();
+        assert!(format!("{:#}", error).contains("Expected an array of objects"));
+    }
+
+    #[test]
+    fn expands_params_from_csv() {
+        let source = source(
+            json!({ "type": "csv", "file": CSV_FILE }),
+            json!({ "params": { "slug": "post" }, "lastmod": "published", "label": "title" }),
+        );
+        let routes = expand_with_source(&blog_route(), &source).unwrap();
+
+        let paths: Vec<&str> = routes.iter().map(|route| route.route.as_str()).collect();
+        assert_eq!(paths, ["/blog/hello-world", "/blog/second-post"]);
+        assert_eq!(routes[0].last_modified, date("2023-11-14T00:00:00Z"));
+        assert_eq!(routes[1].label, "Second Post");
+        // An empty cell keeps the file's date
+        assert_eq!(routes[1].last_modified, None);
+    }
+
+    #[cfg(unix)]
+    fn command_source(command: &str) -> DynamicParamSource {
+        source(json!({ "type": "command", "command": command }), json!({}))
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn expands_params_from_command_output() {
+        let output = r#"printf '{"slug":"hello-world"}\n\n{"slug":"second-post"}\n'"#;
+        let routes = expand_with_source(&blog_route(), &command_source(output)).unwrap();
+        let paths: Vec<&str> = routes.iter().map(|route| route.route.as_str()).collect();
+        assert_eq!(paths, ["/blog/hello-world", "/blog/second-post"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_command_lines_that_are_not_json_objects() {
+        let output = r#"printf '{"slug":"hello-world"}\nnot json\n'"#;
+        let error = expand_with_source(&blog_route(), &command_source(output)).unwrap_err();
+        assert!(
+            format!("{:#}", error).contains("Line 2 of the output"),
+            "{:#}",
+            error
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reports_failing_commands_with_their_stderr() {
+        let error = run_command(
+            "echo 'no database' >&2; exit 3",
+            &[],
+            Duration::from_secs(5),
+        )
+        .unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("Exited with"), "{}", message);
+        assert!(message.contains("no database"), "{}", message);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn kills_commands_that_time_out() {
+        let started = Instant::now();
+        let error = run_command("sleep 10", &[], Duration::from_millis(200)).unwrap_err();
+        assert!(error.to_string().starts_with("Timed out"), "{}", error);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,226 @@
+use crate::RouteInfo;
+use crate::next_config::{NextConfig, Redirect};
+use crate::path_pattern::PathPattern;
+
+// Applies next.config rewrites and redirects: rewrite sources that serve a page become
+// aliases of it, and redirected URLs are dropped
+pub fn apply(routes: Vec<RouteInfo>, next_config: &NextConfig) -> Vec<RouteInfo> {
+    let redirects: Vec<(PathPattern, &Redirect)> = next_config
+        .redirects
+        .iter()
+        .filter_map(|redirect| match PathPattern::parse(&redirect.source) {
+            Some(pattern) => Some((pattern, redirect)),
+            None => {
+                eprintln!(
+                    "Warning: Unsupported redirect source '{}' in next.config",
+                    redirect.source
+                );
+                None
+            }
+        })
+        .collect();
+    let redirect_for = |route: &str| {
+        redirects
+            .iter()
+            .find(|(pattern, _)| pattern.is_match(route))
+            .map(|(_, redirect)| *redirect)
+    };
+
+    let aliases = rewrite_aliases(&routes, next_config);
+    let mut kept: Vec<RouteInfo> = Vec::with_capacity(routes.len() + aliases.len());
+
+    for route in &routes {
+        let Some(redirect) = redirect_for(&route.route) else {
+            continue;
+        };
+
+        let kind = if redirect.permanent {
+            "permanently redirected"
+        } else {
+            "redirected"
+        };
+        println!(
+            "Excluding {} from sitemaps: {} to {} by next.config",
+            route.route, kind, redirect.destination
+        );
+
+        // A rewrite alias keeps the page reachable under another URL
+        let has_alias = aliases
+            .iter()
+            .any(|(origin, alias)| *origin == route.route && redirect_for(&alias.route).is_none());
+        if !has_alias {
+            eprintln!(
+                "Warning: {} has no URL left in the sitemaps: {} is {} to {}",
+                route.path, route.route, kind, redirect.destination
+            );
+        }
+    }
+
+    let aliases = aliases.into_iter().map(|(_, alias)| alias);
+    for route in routes.into_iter().chain(aliases) {
+        if redirect_for(&route.route).is_none()
+            && !kept.iter().any(|existing| existing.route == route.route)
+        {
+            kept.push(route);
+        }
+    }
+
+    kept
+}
+
+// Concrete routes for rewrite sources, e.g. /docs/:path* => /documentation/:path* turns
+// /documentation/intro into an alias at /docs/intro. Each alias is paired with its route.
+fn rewrite_aliases(routes: &[RouteInfo], next_config: &NextConfig) -> Vec<(String, RouteInfo)> {
+    let mut aliases = Vec::new();
+
+    for rewrite in &next_config.rewrites {
+        // Rewrites to other hosts don't expose pages of this project
+        if !rewrite.destination.starts_with('/') {
+            continue;
+        }
+
+        let destination = strip_query(&rewrite.destination);
+        let (Some(source), Some(destination)) = (
+            PathPattern::parse(&rewrite.source),
+            PathPattern::parse(destination),
+        ) else {
+            eprintln!(
+                "Warning: Unsupported rewrite '{}' => '{}' in next.config",
+                rewrite.source, rewrite.destination
+            );
+            continue;
+        };
+
+        for route in routes {
+            let Some(alias_route) = destination
+                .captures(&route.route)
+                .and_then(|values| source.fill(&values))
+            else {
+                continue;
+            };
+            if alias_route == route.route {
+                continue;
+            }
+
+            let mut alias = route.clone();
+            alias.href = alias_route.clone();
+            alias.route = alias_route;
+            aliases.push((route.route.clone(), alias));
+        }
+    }
+
+    aliases
+}
+
+// Destinations can pass values on in a query string, /search?q=:q
+fn strip_query(destination: &str) -> &str {
+    let bytes = destination.as_bytes();
+    for (index, c) in bytes.iter().enumerate() {
+        if *c != b'?' {
+            continue;
+        }
+        // A ? right after a param or group is an optional modifier
+        let before =
+            destination[..index].trim_end_matches(|c: char| c.is_ascii_alphanumeric() || c == '_');
+        let is_modifier =
+            (before.ends_with(':') && before.len() < index) || destination[..index].ends_with(')');
+        if !is_modifier {
+            return &destination[..index];
+        }
+    }
+    destination
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::next_config::Rewrite;
+    use serde_json::json;
+
+    fn route(path: &str) -> RouteInfo {
+        serde_json::from_value(json!({
+            "route": path,
+            "href": path,
+            "path": "app/page.tsx",
+            "label": "",
+            "description": "",
+            "last_modified": null,
+        }))
+        .unwrap()
+    }
+
+    fn routes(result: &[RouteInfo]) -> Vec<&str> {
+        result.iter().map(|route| route.route.as_str()).collect()
+    }
+
+    #[test]
+    fn strips_query_strings() {
+        assert_eq!(strip_query("/search?q=:q"), "/search");
+        assert_eq!(strip_query("/docs/:path*?from=old"), "/docs/:path*");
+        assert_eq!(strip_query("/post/:id?"), "/post/:id?");
+        assert_eq!(
+            strip_query("/post/:id?/comments?page=1"),
+            "/post/:id?/comments"
+        );
+        assert_eq!(strip_query("/(.*)?"), "/(.*)?");
+        assert_eq!(strip_query("/about"), "/about");
+    }
+
+    #[test]
+    fn drops_redirected_routes() {
+        let next_config = NextConfig {
+            redirects: vec![Redirect {
+                source: "/old/:path*".to_string(),
+                destination: "/new/:path*".to_string(),
+                permanent: true,
+            }],
+            ..NextConfig::default()
+        };
+        let result = apply(
+            vec![route("/old"), route("/old/a"), route("/new/a")],
+            &next_config,
+        );
+        assert_eq!(routes(&result), ["/new/a"]);
+    }
+
+    #[test]
+    fn adds_rewrite_aliases_through_query_destinations() {
+        let next_config = NextConfig {
+            rewrites: vec![
+                Rewrite {
+                    source: "/guides/:path*".to_string(),
+                    destination: "/docs/:path*?from=guides".to_string(),
+                },
+                Rewrite {
+                    source: "/proxy/:path*".to_string(),
+                    destination: "https://example.com/:path*".to_string(),
+                },
+            ],
+            ..NextConfig::default()
+        };
+        let result = apply(vec![route("/docs"), route("/docs/intro")], &next_config);
+        assert_eq!(
+            routes(&result),
+            ["/docs", "/docs/intro", "/guides", "/guides/intro"]
+        );
+        assert_eq!(result[3].href, "/guides/intro");
+    }
+
+    #[test]
+    fn keeps_aliases_of_redirected_pages() {
+        let next_config = NextConfig {
+            redirects: vec![Redirect {
+                source: "/internal/:slug".to_string(),
+                destination: "/".to_string(),
+                permanent: false,
+            }],
+            rewrites: vec![Rewrite {
+                source: "/public/:slug".to_string(),
+                destination: "/internal/:slug".to_string(),
+            }],
+            ..NextConfig::default()
+        };
+        let result = apply(vec![route("/internal/report")], &next_config);
+        assert_eq!(routes(&result), ["/public/report"]);
+    }
+}
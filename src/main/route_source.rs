@@ -0,0 +1,66 @@
+use anyhow::Result;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::RouteInfo;
+use crate::config::{Framework, I18nConfig, ScannerConfig};
+use crate::next_source::NextSource;
+use crate::nuxt::NuxtSource;
+use crate::sveltekit::SvelteKitSource;
+use crate::urls::UrlOptions;
+
+// Everything framework specific: how routes are found, which routing rules apply to them
+// and how they become URLs. Filtering and the sitemap generators only see RouteInfo.
+pub trait RouteSource {
+    fn scan(&self, project_path: &Path, options: &ScannerConfig) -> Result<ScanResult>;
+
+    // Routing rules applied once dynamic routes are expanded, e.g. redirects and rewrites
+    fn resolve(&self, _project_path: &Path, routes: Vec<RouteInfo>) -> Result<Vec<RouteInfo>> {
+        Ok(routes)
+    }
+
+    // Base path, trailing slash and static export settings from the framework's own config
+    fn url_options(&self) -> UrlOptions {
+        UrlOptions::default()
+    }
+
+    // Locales declared in the framework's own config, used when smg.config.json has none
+    fn locales(&self, _project_path: &Path) -> Result<Option<I18nConfig>> {
+        Ok(None)
+    }
+}
+
+pub fn load(framework: Framework, project_path: &Path) -> Result<Box<dyn RouteSource>> {
+    Ok(match framework {
+        Framework::Next => Box::new(NextSource::load(project_path)?),
+        Framework::SvelteKit => Box::new(SvelteKitSource::load(project_path)?),
+        Framework::Nuxt => Box::new(NuxtSource::load(project_path)?),
+    })
+}
+
+pub struct ScanResult {
+    pub routes: Vec<RouteInfo>,
+    // Entries that were skipped, with the reason
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(path: &Path, message: impl fmt::Display) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Skipped {}: {}", self.path.display(), self.message)
+    }
+}
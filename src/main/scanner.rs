@@ -1,133 +1,709 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::Utc;
+use regex::Regex;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
 
-use crate::RouteInfo;
+use crate::config::ScannerConfig;
+use crate::expand::{self, ParamValues};
+use crate::ignore_rules::IgnoreRules;
+use crate::literal;
+use crate::page_metadata::{self, PageMetadata};
+use crate::route_source::{Diagnostic, ScanResult};
+use crate::{EntryKind, ParamKind, RouteInfo, RouteParam};
 
-pub fn scan_project(project_path: &Path) -> Result<Vec<RouteInfo>> {
+pub fn scan_project(
+    project_path: &Path,
+    options: &ScannerConfig,
+    page_extensions: &[String],
+) -> Result<ScanResult> {
+    let app_dir = find_router_directory(project_path, "app");
+    let pages_dir = find_router_directory(project_path, "pages");
+
+    if app_dir.is_none() && pages_dir.is_none() {
+        anyhow::bail!("Could not find app or pages directory in project");
+    }
+
+    let mut ignores = IgnoreRules::load(project_path, options.respect_gitignore);
+    let mut diagnostics = Vec::new();
     let mut routes = Vec::new();
 
-    // Find app directory
-    let app_dir = find_app_directory(project_path)?;
+    if let Some(app_dir) = &app_dir {
+        routes.extend(scan_app_router(
+            project_path,
+            app_dir,
+            options,
+            page_extensions,
+            &mut ignores,
+            &mut diagnostics,
+        ));
+    }
+
+    // Projects migrating between routers can have both; app routes take precedence
+    if let Some(pages_dir) = &pages_dir {
+        for route in scan_pages_router(
+            project_path,
+            pages_dir,
+            options,
+            page_extensions,
+            &mut ignores,
+            &mut diagnostics,
+        ) {
+            if !routes.iter().any(|existing| existing.route == route.route) {
+                routes.push(route);
+            }
+        }
+    }
+
+    let routes = with_optional_catch_all_parents(routes);
+    Ok(ScanResult {
+        routes: expand_static_params(project_path, routes),
+        diagnostics,
+    })
+}
+
+// Dynamic pages that list their params as a literal (generateStaticParams / getStaticPaths)
+// become concrete routes; anything else keeps its placeholder
+fn expand_static_params(project_path: &Path, routes: Vec<RouteInfo>) -> Vec<RouteInfo> {
+    let mut expanded: Vec<RouteInfo> = Vec::with_capacity(routes.len());
+
+    for route in routes {
+        let concrete = static_params(&project_path.join(&route.path))
+            .filter(|_| !route.params.is_empty())
+            .and_then(|values| expand::expand_route(&route, &values).ok());
+
+        for entry in concrete.unwrap_or_else(|| vec![route]) {
+            if !expanded
+                .iter()
+                .any(|existing| existing.route == entry.route)
+            {
+                expanded.push(entry);
+            }
+        }
+    }
+
+    expanded
+}
+
+fn static_params(page_path: &Path) -> Option<Vec<ParamValues>> {
+    let source = literal::strip_comments(&fs::read_to_string(page_path).ok()?);
+
+    if let Some(params) = literal::function_return(&source, "generateStaticParams") {
+        return params
+            .as_array()?
+            .iter()
+            .map(ParamValues::from_json)
+            .collect();
+    }
+
+    // getStaticPaths returns { paths: [{ params: { ... } }], fallback }
+    let paths = literal::function_return(&source, "getStaticPaths")?;
+    paths
+        .get("paths")?
+        .as_array()?
+        .iter()
+        .map(|path| ParamValues::from_json(path.get("params")?))
+        .collect()
+}
+
+// An optional catch-all also matches zero segments, so the route without its segment is
+// emitted as a concrete entry: /docs/:slug* => /docs, /:path*/edit => /edit
+pub fn with_optional_catch_all_parents(mut routes: Vec<RouteInfo>) -> Vec<RouteInfo> {
+    let mut parents = Vec::new();
+
+    for route in &routes {
+        let Some(index) = route
+            .params
+            .iter()
+            .position(|param| param.kind == ParamKind::OptionalCatchAll)
+        else {
+            continue;
+        };
 
-    // Walk through all files
-    for entry in WalkDir::new(&app_dir).into_iter().filter_map(|e| e.ok()) {
+        let segment = route.params[index].to_segment();
+        let segments: Vec<&str> = route
+            .route
+            .split('/')
+            .filter(|part| !part.is_empty() && *part != segment)
+            .collect();
+        let parent_route = format!("/{}", segments.join("/"));
+
+        let already_present = routes
+            .iter()
+            .chain(parents.iter())
+            .any(|existing: &RouteInfo| existing.route == parent_route);
+        if already_present {
+            continue;
+        }
+
+        let mut parent = route.clone();
+        parent.params.remove(index);
+        parent.label = default_label_for_route(&parent_route);
+        parent.href = parent_route.clone();
+        parent.route = parent_route;
+        parents.push(parent);
+    }
+
+    routes.extend(parents);
+    routes
+}
+
+fn scan_app_router(
+    project_path: &Path,
+    app_dir: &Path,
+    options: &ScannerConfig,
+    page_extensions: &[String],
+    ignores: &mut IgnoreRules,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Vec<RouteInfo> {
+    let mut routes: Vec<RouteInfo> = Vec::new();
+    let mut layouts = HashMap::new();
+    let mut skipped = Vec::new();
+
+    for entry in ignores.walk(app_dir, options.follow_symlinks, &mut skipped) {
         let path = entry.path();
 
-        // Check if this is a page.tsx or page.js file
-        if is_page_file(path) {
+        // Route handlers (route.ts) and metadata files (icon.png, opengraph-image.tsx, ...)
+        if let Some(entry_kind) = non_page_entry(path, page_extensions) {
+            if is_excluded_path(app_dir, path)
+                || (!options.include_intercepting_routes && is_intercepting_path(app_dir, path))
+            {
+                continue;
+            }
+
+            let info =
+                match non_page_route_info(project_path, app_dir, path, entry_kind, page_extensions)
+                {
+                    Ok(info) => info,
+                    Err(error) => {
+                        diagnostics.push(Diagnostic::new(path, format!("{:#}", error)));
+                        continue;
+                    }
+                };
+            if !routes.iter().any(|existing| existing.route == info.route) {
+                routes.push(info);
+            }
+            continue;
+        }
+
+        // Check if this is a page.tsx, page.mdx, ... file
+        if is_page_file(path, page_extensions) {
             // Skip if in api directory or starts with underscore
-            if is_excluded_path(path) {
+            if is_excluded_path(app_dir, path) {
                 continue;
             }
 
-            let route = path_to_route(&app_dir, path)?;
-            let rel_path = path
-                .strip_prefix(project_path)?
-                .to_string_lossy()
-                .to_string();
-
-            // Get last modified time
-            let metadata = fs::metadata(path)?;
-            let last_modified = metadata.modified().ok().map(|time| {
-                let datetime = chrono::DateTime::<Utc>::from(time);
-                datetime
-            });
+            // Intercepting routes render another route in place, they are not canonical URLs
+            if !options.include_intercepting_routes && is_intercepting_path(app_dir, path) {
+                continue;
+            }
 
-            let label = default_label_for_route(&route);
-            routes.push(RouteInfo {
-                route,
-                path: rel_path,
-                label,
-                description: String::new(), // Empty by default
-                last_modified,
+            // Parallel slots and intercepting routes can resolve to an already scanned route,
+            // in which case the canonical page wins
+            let info = path_to_route(app_dir, path).and_then(|(route, params)| {
+                let layout_chain = layout_chain(app_dir, path, page_extensions, &mut layouts);
+                route_info(project_path, path, route, params, &layout_chain)
             });
+            let info = match info {
+                Ok(info) => info,
+                Err(error) => {
+                    diagnostics.push(Diagnostic::new(path, format!("{:#}", error)));
+                    continue;
+                }
+            };
+            match routes
+                .iter()
+                .position(|existing| existing.route == info.route)
+            {
+                Some(index) => {
+                    if is_slot_or_intercepting(&routes[index].path)
+                        && !is_slot_or_intercepting(&info.path)
+                    {
+                        routes[index] = info;
+                    }
+                }
+                None => routes.push(info),
+            }
+        }
+    }
+
+    diagnostics.extend(skipped);
+    routes
+}
+
+fn scan_pages_router(
+    project_path: &Path,
+    pages_dir: &Path,
+    options: &ScannerConfig,
+    page_extensions: &[String],
+    ignores: &mut IgnoreRules,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Vec<RouteInfo> {
+    let mut routes = Vec::new();
+    let mut skipped = Vec::new();
+
+    for entry in ignores.walk(pages_dir, options.follow_symlinks, &mut skipped) {
+        let path = entry.path();
+
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let Some(stem) = page_stem(path, page_extensions) else {
+            continue;
+        };
+
+        if is_excluded_pages_path(pages_dir, path, stem) {
+            continue;
+        }
+
+        match pages_path_to_route(pages_dir, path, stem)
+            .and_then(|(route, params)| route_info(project_path, path, route, params, &[]))
+        {
+            Ok(info) => routes.push(info),
+            Err(error) => diagnostics.push(Diagnostic::new(path, format!("{:#}", error))),
         }
     }
 
-    Ok(routes)
+    diagnostics.extend(skipped);
+    routes
 }
 
-fn find_app_directory(project_path: &Path) -> Result<PathBuf> {
-    // Check for app directory in project root or src/
-    let app_dir = project_path.join("app");
-    if app_dir.exists() {
-        return Ok(app_dir);
+fn route_info(
+    project_path: &Path,
+    path: &Path,
+    route: String,
+    params: Vec<RouteParam>,
+    layouts: &[PageMetadata],
+) -> Result<RouteInfo> {
+    let mut info = file_route_info(project_path, path, route, params)?;
+
+    // Exported page metadata provides the defaults for label and description
+    let page_metadata = page_metadata::read(path).unwrap_or_default();
+    if let Some(label) = page_metadata.title.as_ref().and_then(|title| title.label()) {
+        info.label = label.to_string();
     }
+    info.description = page_metadata.description.clone().unwrap_or_default();
+    info.title = page_metadata::document_title(layouts, &page_metadata).unwrap_or_default();
+    info.noindex = page_metadata::noindex_source(layouts, &page_metadata).map(|source| {
+        let source = source.strip_prefix(project_path).unwrap_or(source);
+        format!("robots.index is false in {}", source.display())
+    });
+
+    Ok(info)
+}
+
+// A page entry with what every framework has: its file, modification time and a default label
+pub fn file_route_info(
+    project_path: &Path,
+    path: &Path,
+    route: String,
+    params: Vec<RouteParam>,
+) -> Result<RouteInfo> {
+    let rel_path = path
+        .strip_prefix(project_path)?
+        .to_string_lossy()
+        .to_string();
 
-    let src_app_dir = project_path.join("src").join("app");
-    if src_app_dir.exists() {
-        return Ok(src_app_dir);
+    // Get last modified time
+    let metadata = fs::metadata(path).context("Failed to read file metadata")?;
+    let last_modified = metadata.modified().ok().map(chrono::DateTime::<Utc>::from);
+
+    Ok(RouteInfo {
+        href: route.clone(),
+        label: default_label_for_route(&route),
+        route,
+        path: rel_path,
+        title: String::new(),
+        description: String::new(),
+        last_modified,
+        params,
+        locale: None,
+        alternates: Vec::new(),
+        noindex: None,
+        guarded_by: None,
+        entry: EntryKind::Page,
+        methods: Vec::new(),
+    })
+}
+
+fn non_page_route_info(
+    project_path: &Path,
+    app_dir: &Path,
+    path: &Path,
+    entry_kind: EntryKind,
+    page_extensions: &[String],
+) -> Result<RouteInfo> {
+    let (mut route, params) = path_to_route(app_dir, path)?;
+
+    let mut methods = Vec::new();
+    if entry_kind == EntryKind::RouteHandler {
+        methods = exported_methods(path);
+    } else if let Some(file_route) = metadata_file_route(path, page_extensions) {
+        push_segment(&mut route, &file_route);
     }
 
-    anyhow::bail!("Could not find app directory in project")
+    let mut info = route_info(project_path, path, route, params, &[])?;
+    info.entry = entry_kind;
+    info.methods = methods;
+    Ok(info)
 }
 
-fn is_page_file(path: &Path) -> bool {
-    if let Some(file_name) = path.file_name() {
-        let file_name = file_name.to_string_lossy();
-        file_name == "page.tsx" || file_name == "page.js" || file_name == "page.jsx"
-    } else {
-        false
+const HTTP_METHODS: [&str; 7] = ["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"];
+
+// Generated metadata routes and the URL each is served at
+const GENERATED_METADATA_FILES: [(&str, &str); 7] = [
+    ("icon", "icon"),
+    ("apple-icon", "apple-icon"),
+    ("opengraph-image", "opengraph-image"),
+    ("twitter-image", "twitter-image"),
+    ("sitemap", "sitemap.xml"),
+    ("robots", "robots.txt"),
+    ("manifest", "manifest.webmanifest"),
+];
+
+const STATIC_METADATA_FILES: [(&str, &[&str]); 8] = [
+    ("favicon", &["ico"]),
+    ("icon", &["ico", "jpg", "jpeg", "png", "svg"]),
+    ("apple-icon", &["jpg", "jpeg", "png"]),
+    ("opengraph-image", &["jpg", "jpeg", "png", "gif"]),
+    ("twitter-image", &["jpg", "jpeg", "png", "gif"]),
+    ("sitemap", &["xml"]),
+    ("robots", &["txt"]),
+    ("manifest", &["json", "webmanifest"]),
+];
+
+fn non_page_entry(path: &Path, page_extensions: &[String]) -> Option<EntryKind> {
+    if page_stem(path, page_extensions) == Some("route") {
+        return Some(EntryKind::RouteHandler);
     }
+
+    metadata_file_route(path, page_extensions).map(|_| EntryKind::MetadataFile)
 }
 
-fn is_excluded_path(path: &Path) -> bool {
-    let path_str = path.to_string_lossy();
-    path_str.contains("/api/")
-        || path_str.contains("/_")
-        || path.components().any(|c| {
-            if let std::path::Component::Normal(name) = c {
-                let name = name.to_string_lossy();
-                name.starts_with('_') || name == "api"
-            } else {
-                false
-            }
+// The last URL segment of a metadata file, e.g. icon.png => icon.png, robots.ts => robots.txt
+fn metadata_file_route(path: &Path, page_extensions: &[String]) -> Option<String> {
+    if let Some(stem) = page_stem(path, page_extensions) {
+        return GENERATED_METADATA_FILES
+            .iter()
+            .find(|(name, _)| *name == stem)
+            .map(|(_, route)| route.to_string());
+    }
+
+    let file_name = path.file_name()?.to_str()?;
+    let (stem, ext) = file_name.rsplit_once('.')?;
+    STATIC_METADATA_FILES
+        .iter()
+        .any(|(name, extensions)| *name == stem && extensions.contains(&ext))
+        .then(|| file_name.to_string())
+}
+
+pub fn exported_methods(path: &Path) -> Vec<String> {
+    let Ok(source) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let source = literal::strip_comments(&source);
+
+    let declarations =
+        Regex::new(r"export\s+(?:async\s+)?(?:function\s*\*?|const|let|var)\s+([A-Z]+)\b")
+            .expect("valid regex");
+    let export_lists = Regex::new(r"export\s*\{([^}]*)\}").expect("valid regex");
+
+    let mut names: Vec<String> = declarations
+        .captures_iter(&source)
+        .map(|captures| captures[1].to_string())
+        .collect();
+    for captures in export_lists.captures_iter(&source) {
+        // export { handler as GET, POST }
+        names.extend(
+            captures[1]
+                .split(',')
+                .filter_map(|item| item.split_whitespace().last())
+                .map(str::to_string),
+        );
+    }
+
+    HTTP_METHODS
+        .iter()
+        .filter(|method| names.iter().any(|name| name == *method))
+        .map(|method| method.to_string())
+        .collect()
+}
+
+// Metadata of every layout from the app directory down to the page, outermost first
+fn layout_chain(
+    app_dir: &Path,
+    page_path: &Path,
+    page_extensions: &[String],
+    cache: &mut HashMap<PathBuf, Option<PageMetadata>>,
+) -> Vec<PageMetadata> {
+    let Some(page_dir) = page_path.parent() else {
+        return Vec::new();
+    };
+
+    let mut dirs: Vec<&Path> = page_dir
+        .ancestors()
+        .take_while(|dir| dir.starts_with(app_dir))
+        .collect();
+    dirs.reverse();
+
+    dirs.into_iter()
+        .filter_map(|dir| {
+            cache
+                .entry(dir.to_path_buf())
+                .or_insert_with(|| {
+                    page_extensions
+                        .iter()
+                        .map(|ext| dir.join(format!("layout.{}", ext)))
+                        .find(|layout| layout.is_file())
+                        .and_then(|layout| page_metadata::read(&layout))
+                })
+                .clone()
         })
+        .collect()
 }
 
-fn path_to_route(app_dir: &Path, page_path: &Path) -> Result<String> {
-    // Remove app_dir prefix and page.tsx filename
+fn find_router_directory(project_path: &Path, name: &str) -> Option<PathBuf> {
+    // Check for the router directory in project root or src/
+    let dir = project_path.join(name);
+    if dir.is_dir() {
+        return Some(dir);
+    }
+
+    let src_dir = project_path.join("src").join(name);
+    if src_dir.is_dir() {
+        return Some(src_dir);
+    }
+
+    None
+}
+
+fn is_page_file(path: &Path, page_extensions: &[String]) -> bool {
+    page_stem(path, page_extensions) == Some("page")
+}
+
+// File name without its page extension, which can contain dots (e.g. "page.tsx" as an extension)
+fn page_stem<'a>(path: &'a Path, page_extensions: &[String]) -> Option<&'a str> {
+    let file_name = path.file_name()?.to_str()?;
+
+    page_extensions
+        .iter()
+        .filter_map(|ext| file_name.strip_suffix(ext.as_str())?.strip_suffix('.'))
+        .filter(|stem| !stem.is_empty())
+        .min_by_key(|stem| stem.len())
+}
+
+// Private folders (_name) anywhere below the app directory, and the top-level api folder
+fn is_excluded_path(app_dir: &Path, path: &Path) -> bool {
+    let Some(relative) = path
+        .parent()
+        .and_then(|parent| parent.strip_prefix(app_dir).ok())
+    else {
+        return true;
+    };
+
+    let folders: Vec<String> = relative
+        .components()
+        .filter_map(|component| match component {
+            std::path::Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect();
+
+    if folders.iter().any(|name| name.starts_with('_')) {
+        return true;
+    }
+
+    // Route groups and slots are not URL segments, so app/(site)/api is still top-level
+    folders
+        .iter()
+        .find(|name| !is_group(name) && !name.starts_with('@'))
+        .is_some_and(|name| name == "api")
+}
+
+pub fn is_group(name: &str) -> bool {
+    name.starts_with('(') && name.ends_with(')')
+}
+
+// %5F is Next's escape for a URL segment that starts with an underscore
+fn unescape_underscore(name: &str) -> String {
+    match name
+        .strip_prefix("%5F")
+        .or_else(|| name.strip_prefix("%5f"))
+    {
+        Some(rest) => format!("_{}", rest),
+        None => name.to_string(),
+    }
+}
+
+const PAGES_ROUTER_SPECIAL_FILES: [&str; 5] = ["_app", "_document", "_error", "404", "500"];
+
+fn is_excluded_pages_path(pages_dir: &Path, path: &Path, stem: &str) -> bool {
+    let Ok(relative) = path.strip_prefix(pages_dir) else {
+        return true;
+    };
+
+    if let Some(std::path::Component::Normal(first)) = relative.components().next()
+        && first == "api"
+    {
+        return true;
+    }
+
+    // Only files directly under pages/ are special, pages/errors/404.tsx is a regular page
+    relative.parent() == Some(Path::new("")) && PAGES_ROUTER_SPECIAL_FILES.contains(&stem)
+}
+
+fn pages_path_to_route(
+    pages_dir: &Path,
+    page_path: &Path,
+    stem: &str,
+) -> Result<(String, Vec<RouteParam>)> {
     let parent = page_path.parent().unwrap_or(page_path);
-    let relative = parent.strip_prefix(app_dir)?;
+    let relative = parent.strip_prefix(pages_dir)?.join(stem);
 
-    // Convert to route
     let mut route = String::from("/");
+    let mut params = Vec::new();
     for component in relative.components() {
         if let std::path::Component::Normal(name) = component {
             let name = name.to_string_lossy();
 
-            // Skip directories wrapped in parentheses
-            if name.starts_with('(') && name.ends_with(')') {
+            // index files map to their parent directory
+            if name == "index" {
                 continue;
             }
 
-            // Handle dynamic routes
-            let segment = if name.starts_with('[') && name.ends_with(']') {
-                // For [id] or [...slug]
-                format!(":{}", &name[1..name.len() - 1])
-            } else {
-                name.to_string()
-            };
+            let (segment, param) = parse_segment(&name);
+            params.extend(param);
+            push_segment(&mut route, &segment);
+        }
+    }
 
-            if !route.ends_with('/') {
-                route.push('/');
+    Ok((route, params))
+}
+
+fn path_to_route(app_dir: &Path, page_path: &Path) -> Result<(String, Vec<RouteParam>)> {
+    // Remove app_dir prefix and page.tsx filename
+    let parent = page_path.parent().unwrap_or(page_path);
+    let relative = parent.strip_prefix(app_dir)?;
+
+    // Convert to route segments
+    let mut segments: Vec<(String, Option<RouteParam>)> = Vec::new();
+    for component in relative.components() {
+        if let std::path::Component::Normal(name) = component {
+            let mut name = name.to_string_lossy().to_string();
+
+            // Parallel route slots are not part of the URL
+            if name.starts_with('@') {
+                continue;
+            }
+
+            // Intercepting routes resolve relative to route segments, not folders
+            if let Some((interception, rest)) = parse_interception(&name) {
+                match interception {
+                    Interception::SameLevel => {}
+                    Interception::Up(levels) => {
+                        segments.truncate(segments.len().saturating_sub(levels));
+                    }
+                    Interception::Root => segments.clear(),
+                }
+                name = rest.to_string();
+            }
+
+            // Skip directories wrapped in parentheses
+            if is_group(&name) {
+                continue;
             }
-            route.push_str(&segment);
+
+            // Handle dynamic routes
+            segments.push(parse_segment(&unescape_underscore(&name)));
         }
     }
 
-    // Handle root route
-    if route == "/" && page_path.file_name().unwrap_or_default() == "page.tsx" {
-        return Ok("/".to_string());
+    let mut route = String::from("/");
+    let mut params = Vec::new();
+    for (segment, param) in segments {
+        push_segment(&mut route, &segment);
+        params.extend(param);
+    }
+
+    Ok((route, params))
+}
+
+enum Interception {
+    SameLevel,
+    Up(usize),
+    Root,
+}
+
+// (.)photo, (..)photo, (..)(..)photo and (...)photo
+fn parse_interception(name: &str) -> Option<(Interception, &str)> {
+    if let Some(rest) = name.strip_prefix("(...)") {
+        return Some((Interception::Root, rest));
+    }
+
+    if let Some(rest) = name.strip_prefix("(.)") {
+        return Some((Interception::SameLevel, rest));
     }
 
-    Ok(route)
+    let mut rest = name;
+    let mut levels = 0;
+    while let Some(stripped) = rest.strip_prefix("(..)") {
+        rest = stripped;
+        levels += 1;
+    }
+
+    (levels > 0).then_some((Interception::Up(levels), rest))
+}
+
+fn is_intercepting_path(app_dir: &Path, page_path: &Path) -> bool {
+    page_path
+        .strip_prefix(app_dir)
+        .map(|relative| {
+            relative
+                .components()
+                .any(|c| parse_interception(&c.as_os_str().to_string_lossy()).is_some())
+        })
+        .unwrap_or(false)
+}
+
+fn is_slot_or_intercepting(rel_path: &str) -> bool {
+    Path::new(rel_path).components().any(|c| {
+        let name = c.as_os_str().to_string_lossy();
+        name.starts_with('@') || parse_interception(&name).is_some()
+    })
 }
 
-fn default_label_for_route(route: &str) -> String {
+// [id] => :id, [...slug] => :slug+, [[...slug]] => :slug*
+fn parse_segment(name: &str) -> (String, Option<RouteParam>) {
+    let (inner, kind) = if let Some(inner) = name
+        .strip_prefix("[[...")
+        .and_then(|n| n.strip_suffix("]]"))
+    {
+        (inner, ParamKind::OptionalCatchAll)
+    } else if let Some(inner) = name.strip_prefix("[...").and_then(|n| n.strip_suffix(']')) {
+        (inner, ParamKind::CatchAll)
+    } else if let Some(inner) = name.strip_prefix('[').and_then(|n| n.strip_suffix(']')) {
+        (inner, ParamKind::Dynamic)
+    } else {
+        return (name.to_string(), None);
+    };
+
+    let param = RouteParam {
+        name: inner.to_string(),
+        kind,
+    };
+    (param.to_segment(), Some(param))
+}
+
+pub fn push_segment(route: &mut String, segment: &str) {
+    if !route.ends_with('/') {
+        route.push('/');
+    }
+    route.push_str(segment);
+}
+
+pub fn default_label_for_route(route: &str) -> String {
     if route == "/" {
         return "Home".to_string();
     }
@@ -136,8 +712,9 @@ fn default_label_for_route(route: &str) -> String {
     let segments: Vec<&str> = route.split('/').filter(|s| !s.is_empty()).collect();
     if let Some(last) = segments.last() {
         // Handle dynamic routes
-        if last.starts_with(':') {
-            return format!("{} Detail", capitalize_words(&last[1..].replace('-', " ")));
+        if let Some(param) = last.strip_prefix(':') {
+            let param = param.trim_end_matches(['+', '*']);
+            return format!("{} Detail", capitalize_words(&param.replace('-', " ")));
         }
 
         // Replace hyphens with spaces and capitalize each word
@@ -159,3 +736,200 @@ fn capitalize_words(s: &str) -> String {
         .collect::<Vec<String>>()
         .join(" ")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    const APP: &str = "/project/app";
+
+    fn app_route(file: &str) -> String {
+        let app = Path::new(APP);
+        path_to_route(app, &app.join(file)).unwrap().0
+    }
+
+    fn pages_route(file: &str, stem: &str) -> (String, Vec<RouteParam>) {
+        let pages = Path::new("/project/pages");
+        pages_path_to_route(pages, &pages.join(file), stem).unwrap()
+    }
+
+    fn param(name: &str, kind: ParamKind) -> RouteParam {
+        RouteParam {
+            name: name.to_string(),
+            kind,
+        }
+    }
+
+    fn scanned(route: &str, params: Value) -> RouteInfo {
+        serde_json::from_value(json!({
+            "route": route,
+            "href": route,
+            "path": "app/page.tsx",
+            "label": "",
+            "description": "",
+            "last_modified": null,
+            "params": params,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn excludes_private_folders_and_the_top_level_api() {
+        let excluded = |app: &str, file: &str| {
+            let app = Path::new(app);
+            is_excluded_path(app, &app.join(file))
+        };
+
+        assert!(excluded(APP, "_components/page.tsx"));
+        assert!(excluded(APP, "blog/_drafts/page.tsx"));
+        assert!(excluded(APP, "api/users/route.ts"));
+        assert!(excluded(APP, "(site)/api/route.ts"));
+        assert!(excluded(APP, "@modal/api/route.ts"));
+
+        // Only folders below the app directory count
+        assert!(!excluded("/home/me/_work/site/app", "about/page.tsx"));
+        assert!(!excluded(APP, "blog/api/page.tsx"));
+        assert!(!excluded(APP, "%5Fdrafts/page.tsx"));
+        assert!(!excluded(APP, "page.tsx"));
+    }
+
+    #[test]
+    fn unescapes_leading_underscores() {
+        assert_eq!(unescape_underscore("%5Fdrafts"), "_drafts");
+        assert_eq!(unescape_underscore("%5fdrafts"), "_drafts");
+        assert_eq!(unescape_underscore("a%5Fb"), "a%5Fb");
+        assert_eq!(unescape_underscore("drafts"), "drafts");
+    }
+
+    #[test]
+    fn maps_app_router_folders_to_routes() {
+        assert_eq!(app_route("page.tsx"), "/");
+        assert_eq!(app_route("(marketing)/about/page.tsx"), "/about");
+        assert_eq!(app_route("@modal/login/page.tsx"), "/login");
+        assert_eq!(app_route("%5Fdrafts/page.tsx"), "/_drafts");
+        assert_eq!(app_route("blog/[slug]/page.tsx"), "/blog/:slug");
+        assert_eq!(app_route("shop/[...path]/page.tsx"), "/shop/:path+");
+        assert_eq!(app_route("docs/[[...slug]]/page.tsx"), "/docs/:slug*");
+
+        let app = Path::new(APP);
+        let (_, params) = path_to_route(app, &app.join("[locale]/[...slug]/page.tsx")).unwrap();
+        assert_eq!(
+            params,
+            [
+                param("locale", ParamKind::Dynamic),
+                param("slug", ParamKind::CatchAll)
+            ]
+        );
+    }
+
+    #[test]
+    fn resolves_intercepting_routes_by_segment() {
+        assert_eq!(app_route("feed/(.)photo/[id]/page.tsx"), "/feed/photo/:id");
+        assert_eq!(app_route("feed/(..)photo/[id]/page.tsx"), "/photo/:id");
+        assert_eq!(
+            app_route("shop/items/(..)(..)photo/[id]/page.tsx"),
+            "/photo/:id"
+        );
+        assert_eq!(app_route("a/b/c/(...)photo/page.tsx"), "/photo");
+        // Groups and slots are not segments, so (..) skips past them to the URL segment
+        assert_eq!(
+            app_route("feed/(group)/@modal/(..)photo/page.tsx"),
+            "/photo"
+        );
+
+        assert!(matches!(
+            parse_interception("(..)(..)photo"),
+            Some((Interception::Up(2), "photo"))
+        ));
+        assert!(matches!(
+            parse_interception("(...)photo"),
+            Some((Interception::Root, "photo"))
+        ));
+        assert!(matches!(
+            parse_interception("(.)photo"),
+            Some((Interception::SameLevel, "photo"))
+        ));
+        assert!(parse_interception("(group)").is_none());
+        assert!(parse_interception("photo").is_none());
+    }
+
+    #[test]
+    fn maps_pages_router_files_to_routes() {
+        assert_eq!(pages_route("index.tsx", "index").0, "/");
+        assert_eq!(pages_route("blog/index.tsx", "index").0, "/blog");
+        assert_eq!(pages_route("about.tsx", "about").0, "/about");
+
+        let (route, params) = pages_route("blog/[slug].tsx", "[slug]");
+        assert_eq!(route, "/blog/:slug");
+        assert_eq!(params, [param("slug", ParamKind::Dynamic)]);
+
+        assert_eq!(
+            pages_route("docs/[[...slug]].tsx", "[[...slug]]").0,
+            "/docs/:slug*"
+        );
+        // Only index files map to their folder, not index folders
+        assert_eq!(pages_route("index/about.tsx", "about").0, "/about");
+    }
+
+    #[test]
+    fn adds_parents_of_optional_catch_all_routes() {
+        let routes = with_optional_catch_all_parents(vec![
+            scanned(
+                "/docs/:slug*",
+                json!([{ "name": "slug", "kind": "optional_catch_all" }]),
+            ),
+            scanned(
+                "/:all*",
+                json!([{ "name": "all", "kind": "optional_catch_all" }]),
+            ),
+            scanned("/shop", json!([])),
+            scanned(
+                "/shop/:path*",
+                json!([{ "name": "path", "kind": "optional_catch_all" }]),
+            ),
+            // SvelteKit and Nuxt rest params can be followed by more segments
+            scanned(
+                "/:lang/:path*/edit",
+                json!([
+                    { "name": "lang", "kind": "dynamic" },
+                    { "name": "path", "kind": "optional_catch_all" }
+                ]),
+            ),
+        ]);
+
+        let parents: Vec<(&str, &[RouteParam])> = routes[5..]
+            .iter()
+            .map(|route| (route.route.as_str(), route.params.as_slice()))
+            .collect();
+        let lang = [param("lang", ParamKind::Dynamic)];
+        assert_eq!(
+            parents,
+            [
+                ("/docs", &[][..]),
+                ("/", &[][..]),
+                ("/:lang/edit", &lang[..])
+            ]
+        );
+        assert_eq!(routes[5].label, "Docs");
+        assert_eq!(routes[7].href, "/:lang/edit");
+    }
+
+    #[test]
+    fn skips_special_files_only_at_the_pages_root() {
+        let pages = Path::new("/project/pages");
+        let excluded =
+            |file: &str, stem: &str| is_excluded_pages_path(pages, &pages.join(file), stem);
+
+        assert!(excluded("_app.tsx", "_app"));
+        assert!(excluded("_document.tsx", "_document"));
+        assert!(excluded("404.tsx", "404"));
+        assert!(excluded("500.tsx", "500"));
+        assert!(excluded("api/users.ts", "users"));
+
+        assert!(!excluded("errors/404.tsx", "404"));
+        assert!(!excluded("docs/_app.tsx", "_app"));
+        assert!(!excluded("blog/api.tsx", "api"));
+        assert!(!excluded("about.tsx", "about"));
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use serde_json::{from_reader, to_string_pretty};
 use std::collections::HashMap;
 use std::fs::File;
@@ -6,32 +7,68 @@ use std::io::{BufReader, Write};
 use std::path::Path;
 
 use crate::RouteInfo;
+use crate::scanner::default_label_for_route;
+
+// A sitemap.json entry. `generated` records the label and description smg filled in,
+// so the next run can tell hand edits apart from values it should refresh.
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    #[serde(flatten)]
+    route: RouteInfo,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    generated: Option<Generated>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Generated {
+    label: String,
+    #[serde(default)]
+    description: String,
+}
 
 pub fn generate(routes: &[RouteInfo], output_path: &Path) -> Result<()> {
     // Read existing sitemap.json if it exists
-    let mut old_labels: HashMap<String, (String, String)> = HashMap::new();
+    let mut old_entries: HashMap<String, Entry> = HashMap::new();
     if output_path.exists() {
         let file = File::open(output_path)?;
         let reader = BufReader::new(file);
-        if let Ok(existing_routes) = from_reader::<_, Vec<RouteInfo>>(reader) {
-            for route in existing_routes {
-                old_labels.insert(route.route, (route.label, route.description));
+        if let Ok(existing_entries) = from_reader::<_, Vec<Entry>>(reader) {
+            for entry in existing_entries {
+                old_entries.insert(entry.route.route.clone(), entry);
             }
         }
     }
 
-    // Merge
-    let mut merged_routes = Vec::with_capacity(routes.len());
-    for mut route in routes.to_owned() {
-        if let Some((label, description)) = old_labels.get(&route.route) {
-            route.label = label.clone();
-            route.description = description.clone();
+    // Merge, keeping hand-edited values over the generated ones
+    let mut merged_entries = Vec::with_capacity(routes.len());
+    for mut route in routes.iter().cloned() {
+        let generated = Generated {
+            label: route.label.clone(),
+            description: route.description.clone(),
+        };
+
+        if let Some(old) = old_entries.get(&route.route) {
+            // Older files have no `generated`, their labels came from the route
+            let old_generated = old.generated.clone().unwrap_or_else(|| Generated {
+                label: default_label_for_route(&route.route),
+                description: String::new(),
+            });
+            if old.route.label != old_generated.label {
+                route.label = old.route.label.clone();
+            }
+            if old.route.description != old_generated.description {
+                route.description = old.route.description.clone();
+            }
         }
-        merged_routes.push(route);
+
+        merged_entries.push(Entry {
+            route,
+            generated: Some(generated),
+        });
     }
 
     // Write merged routes and descriptions
-    let json = to_string_pretty(&merged_routes)?;
+    let json = to_string_pretty(&merged_entries)?;
     let mut file = File::create(output_path)?;
     file.write_all(json.as_bytes())?;
 
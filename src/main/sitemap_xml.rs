@@ -17,11 +17,14 @@ pub fn generate(routes: &[RouteInfo], output_path: &Path, base_url: &str) -> Res
     })?;
 
     // Urlset start tag with namespaces
-    writer.write(XmlEvent::start_element("urlset")
+    let mut urlset = XmlEvent::start_element("urlset")
         .default_ns("http://www.sitemaps.org/schemas/sitemap/0.9")
         .ns("xsi", "http://www.w3.org/2001/XMLSchema-instance")
-        .attr("xsi:schemaLocation", "http://www.sitemaps.org/schemas/sitemap/0.9 http://www.sitemaps.org/schemas/sitemap/0.9/sitemap.xsd")
-    )?;
+        .attr("xsi:schemaLocation", "http://www.sitemaps.org/schemas/sitemap/0.9 http://www.sitemaps.org/schemas/sitemap/0.9/sitemap.xsd");
+    if routes.iter().any(|route| !route.alternates.is_empty()) {
+        urlset = urlset.ns("xhtml", "http://www.w3.org/1999/xhtml");
+    }
+    writer.write(urlset)?;
 
     // Write each URL entry
     for route in routes {
@@ -29,7 +32,7 @@ pub fn generate(routes: &[RouteInfo], output_path: &Path, base_url: &str) -> Res
 
         // Location
         writer.write(XmlEvent::start_element("loc"))?;
-        let full_url = format!("{}{}", base_url.trim_end_matches('/'), route.route);
+        let full_url = absolute_url(base_url, &route.href);
         writer.write(XmlEvent::characters(&full_url))?;
         writer.write(XmlEvent::end_element())?;
 
@@ -42,6 +45,18 @@ pub fn generate(routes: &[RouteInfo], output_path: &Path, base_url: &str) -> Res
             writer.write(XmlEvent::end_element())?;
         }
 
+        // Localized alternates
+        for alternate in &route.alternates {
+            let href = absolute_url(base_url, &alternate.href);
+            writer.write(
+                XmlEvent::start_element("xhtml:link")
+                    .attr("rel", "alternate")
+                    .attr("hreflang", &alternate.hreflang)
+                    .attr("href", &href),
+            )?;
+            writer.write(XmlEvent::end_element())?;
+        }
+
         writer.write(XmlEvent::end_element())?; // Close url
     }
 
@@ -49,3 +64,7 @@ pub fn generate(routes: &[RouteInfo], output_path: &Path, base_url: &str) -> Res
 
     Ok(())
 }
+
+fn absolute_url(base_url: &str, href: &str) -> String {
+    format!("{}{}", base_url.trim_end_matches('/'), href)
+}
@@ -0,0 +1,250 @@
+use anyhow::{Result, bail};
+use regex::Regex;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::ScannerConfig;
+use crate::expand::{self, ParamValues};
+use crate::file_router;
+use crate::ignore_rules::IgnoreRules;
+use crate::literal;
+use crate::route_source::{Diagnostic, RouteSource, ScanResult};
+use crate::scanner;
+use crate::urls::UrlOptions;
+use crate::{EntryKind, RouteInfo, RouteParam};
+
+const CONFIG_FILES: [&str; 2] = ["svelte.config.js", "svelte.config.ts"];
+const ROUTES_DIR: &str = "src/routes";
+const DEFAULT_EXTENSIONS: [&str; 1] = [".svelte"];
+
+// SvelteKit projects: pages are src/routes/**/+page.svelte, endpoints are +server.{ts,js}
+pub struct SvelteKitSource {
+    extensions: Vec<String>,
+    url_options: UrlOptions,
+}
+
+impl SvelteKitSource {
+    pub fn load(project_path: &Path) -> Result<Self> {
+        let source = file_router::read_config(project_path, &CONFIG_FILES)?;
+        let config = literal::ExportedConfig::parse(&source);
+
+        // extensions: ['.svelte', '.md'] when mdsvex or similar preprocessors are used
+        let extensions = config
+            .get("extensions")
+            .as_ref()
+            .and_then(literal::as_string_list)
+            .unwrap_or_else(|| {
+                DEFAULT_EXTENSIONS
+                    .iter()
+                    .map(|ext| ext.to_string())
+                    .collect()
+            });
+
+        // kit.paths.base
+        let base_path = config
+            .get("kit")
+            .and_then(|kit| kit.get("paths")?.get("base")?.as_str().map(str::to_string));
+
+        // trailingSlash is a page option, set for the whole app in the root layout
+        let trailing_slash =
+            page_option(&project_path.join(ROUTES_DIR), "+layout", "trailingSlash").and_then(
+                |value| match value.as_str()? {
+                    "always" => Some(true),
+                    "never" => Some(false),
+                    _ => None,
+                },
+            );
+
+        Ok(Self {
+            extensions,
+            url_options: UrlOptions::new(
+                base_path.as_deref(),
+                trailing_slash.unwrap_or(false),
+                false,
+            ),
+        })
+    }
+}
+
+impl RouteSource for SvelteKitSource {
+    fn scan(&self, project_path: &Path, options: &ScannerConfig) -> Result<ScanResult> {
+        let routes_dir = project_path.join(ROUTES_DIR);
+        if !routes_dir.is_dir() {
+            bail!("Could not find {} in project", ROUTES_DIR);
+        }
+
+        let mut ignores = IgnoreRules::load(project_path, options.respect_gitignore);
+        let mut diagnostics = Vec::new();
+        let mut skipped = Vec::new();
+        let mut routes: Vec<RouteInfo> = Vec::new();
+
+        for entry in ignores.walk(&routes_dir, options.follow_symlinks, &mut skipped) {
+            let path = entry.path();
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let Some(entry_kind) = self.entry_kind(path) else {
+                continue;
+            };
+
+            let infos = route_variants(&routes_dir, path).and_then(|variants| {
+                variants
+                    .into_iter()
+                    .map(|(route, params)| {
+                        let mut info = scanner::file_route_info(project_path, path, route, params)?;
+                        if entry_kind == EntryKind::RouteHandler {
+                            info.entry = entry_kind;
+                            info.methods = scanner::exported_methods(path);
+                        }
+                        Ok(info)
+                    })
+                    .collect::<Result<Vec<_>>>()
+            });
+
+            match infos {
+                Ok(infos) => {
+                    for info in infos {
+                        if !routes.iter().any(|existing| existing.route == info.route) {
+                            routes.push(info);
+                        }
+                    }
+                }
+                Err(error) => diagnostics.push(Diagnostic::new(path, format!("{:#}", error))),
+            }
+        }
+        diagnostics.extend(skipped);
+
+        let routes = scanner::with_optional_catch_all_parents(routes);
+        Ok(ScanResult {
+            routes: expand_entries(project_path, &routes_dir, routes),
+            diagnostics,
+        })
+    }
+
+    fn url_options(&self) -> UrlOptions {
+        self.url_options.clone()
+    }
+}
+
+impl SvelteKitSource {
+    // +page.svelte (or +page@layout.svelte) is a page, +server.{ts,js} is an endpoint
+    fn entry_kind(&self, path: &Path) -> Option<EntryKind> {
+        let file_name = path.file_name()?.to_str()?;
+
+        if matches!(file_name, "+server.ts" | "+server.js") {
+            return Some(EntryKind::RouteHandler);
+        }
+
+        self.extensions
+            .iter()
+            .filter_map(|ext| file_name.strip_suffix(ext.as_str()))
+            .any(|stem| stem == "+page" || stem.starts_with("+page@"))
+            .then_some(EntryKind::Page)
+    }
+}
+
+// Every route a page file serves: an optional [[param]] matches with and without its segment
+fn route_variants(routes_dir: &Path, path: &Path) -> Result<Vec<(String, Vec<RouteParam>)>> {
+    let parent = path.parent().unwrap_or(path);
+    let names =
+        parent
+            .strip_prefix(routes_dir)?
+            .components()
+            .filter_map(|component| match component {
+                std::path::Component::Normal(name) => Some(unescape(&name.to_string_lossy())),
+                _ => None,
+            });
+
+    file_router::route_variants(names)
+}
+
+// Hex escapes for characters that can't be in folder names: [x+2e] => ., [u+00e9] => é
+fn unescape(name: &str) -> String {
+    let pattern = Regex::new(r"\[(?:x|u)\+([0-9a-fA-F]+)\]").expect("valid regex");
+    pattern
+        .replace_all(name, |captures: &regex::Captures| {
+            u32::from_str_radix(&captures[1], 16)
+                .ok()
+                .and_then(char::from_u32)
+                .map(String::from)
+                .unwrap_or_else(|| captures[0].to_string())
+        })
+        .to_string()
+}
+
+// Prerendered dynamic pages that list their params in a literal `entries` become concrete routes
+fn expand_entries(
+    project_path: &Path,
+    routes_dir: &Path,
+    routes: Vec<RouteInfo>,
+) -> Vec<RouteInfo> {
+    let mut expanded: Vec<RouteInfo> = Vec::with_capacity(routes.len());
+
+    for route in routes {
+        let page_dir = project_path
+            .join(&route.path)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let concrete = Some(&route)
+            .filter(|route| !route.params.is_empty() && is_prerendered(routes_dir, &page_dir))
+            .and_then(|route| expand::expand_route(route, &entries(&page_dir)?).ok());
+
+        for entry in concrete.unwrap_or_else(|| vec![route]) {
+            if !expanded
+                .iter()
+                .any(|existing| existing.route == entry.route)
+            {
+                expanded.push(entry);
+            }
+        }
+    }
+
+    expanded
+}
+
+fn entries(page_dir: &Path) -> Option<Vec<ParamValues>> {
+    option_files(page_dir, "+page")
+        .find_map(|source| literal::function_return(&source, "entries"))?
+        .as_array()?
+        .iter()
+        .map(ParamValues::from_json)
+        .collect()
+}
+
+// `prerender` from the page, or else from the closest layout that sets it
+fn is_prerendered(routes_dir: &Path, page_dir: &Path) -> bool {
+    let value = page_option(page_dir, "+page", "prerender").or_else(|| {
+        page_dir
+            .ancestors()
+            .take_while(|dir| dir.starts_with(routes_dir))
+            .find_map(|dir| page_option(dir, "+layout", "prerender"))
+    });
+
+    match value {
+        Some(Value::Bool(prerender)) => prerender,
+        Some(Value::String(prerender)) => prerender == "auto",
+        _ => false,
+    }
+}
+
+// A literal `export const <name> = ...` in +page.ts, +page.server.ts, +layout.js, ...
+fn page_option(dir: &Path, file: &str, name: &str) -> Option<Value> {
+    let pattern = Regex::new(&format!(
+        r"\bexport\s+const\s+{}\s*(?::[^=]+)?=",
+        regex::escape(name)
+    ))
+    .expect("valid regex");
+
+    option_files(dir, file).find_map(|source| literal::value_after(&source, &pattern))
+}
+
+fn option_files<'a>(dir: &'a Path, file: &'a str) -> impl Iterator<Item = String> + 'a {
+    ["ts", "js", "server.ts", "server.js"]
+        .into_iter()
+        .map(move |ext| dir.join(format!("{}.{}", file, ext)))
+        .filter_map(|path: PathBuf| fs::read_to_string(path).ok())
+        .map(|source| literal::strip_comments(&source))
+}
@@ -0,0 +1,65 @@
+use crate::RouteInfo;
+use crate::config::UrlOverrides;
+
+// How routes are turned into the URLs a deployment actually serves
+#[derive(Debug, Clone, Default)]
+pub struct UrlOptions {
+    pub base_path: String,
+    pub trailing_slash: bool,
+    pub static_export: bool,
+}
+
+impl UrlOptions {
+    pub fn new(base_path: Option<&str>, trailing_slash: bool, static_export: bool) -> Self {
+        Self {
+            base_path: base_path
+                .unwrap_or_default()
+                .trim_end_matches('/')
+                .to_string(),
+            trailing_slash,
+            static_export,
+        }
+    }
+
+    // Settings from smg.config.json win over the ones read from the framework's config
+    pub fn with_overrides(self, overrides: &UrlOverrides) -> Self {
+        Self::new(
+            overrides.base_path.as_deref().or(Some(&self.base_path)),
+            overrides.trailing_slash.unwrap_or(self.trailing_slash),
+            overrides.static_export.unwrap_or(self.static_export),
+        )
+    }
+
+    pub fn href(&self, route: &str) -> String {
+        if route == "/" {
+            return match (self.base_path.is_empty(), self.trailing_slash) {
+                (true, _) => "/".to_string(),
+                (false, true) => format!("{}/", self.base_path),
+                (false, false) => self.base_path.clone(),
+            };
+        }
+
+        let path = format!("{}{}", self.base_path, route.trim_end_matches('/'));
+        if self.trailing_slash {
+            format!("{}/", path)
+        } else if self.static_export {
+            format!("{}.html", path)
+        } else {
+            path
+        }
+    }
+
+    pub fn apply(&self, routes: &mut [RouteInfo]) {
+        for route in routes {
+            // Route handlers and metadata files are served as-is, without a trailing slash or .html
+            route.href = if route.entry.is_page() {
+                self.href(&route.route)
+            } else {
+                format!("{}{}", self.base_path, route.route)
+            };
+            for alternate in &mut route.alternates {
+                alternate.href = self.href(&alternate.route);
+            }
+        }
+    }
+}